@@ -1,3 +1,4 @@
+use crate::state::PriceCurve;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -5,16 +6,75 @@ pub struct FactoryInitialized {
     pub authority: Pubkey,
     pub default_quorum: u64,
     pub default_voting_window: i64,
-    pub platform_fee_percentage: u64,
+    pub platform_fee_bps: u64,
+    pub max_platform_fee_bps: u64,
     pub usdc_mint: Pubkey,
 }
 
+#[event]
+pub struct FactoryUpdated {
+    pub authority: Pubkey,
+    pub platform_fee_bps: u64,
+    pub crank_bounty: u64,
+    pub filing_bond_amount: u64,
+    pub filing_bond_bps: u64,
+    pub misconduct_cosign_threshold: u64,
+    pub inactivity_period: i64,
+    pub default_vault_share_bps: u64,
+    pub min_vault_share_bps: u64,
+    pub max_vault_share_bps: u64,
+    pub vault_share_timelock: i64,
+    pub revocation_cooldown: i64,
+}
+
+#[event]
+pub struct CreatorFeeOverrideSet {
+    pub creator: Pubkey,
+    pub platform_fee_bps: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct CreatorFeeOverrideRemoved {
+    pub creator: Pubkey,
+}
+
 #[event]
 pub struct CreatorPoolCreated {
     pub creator: Pubkey,
     pub pool: Pubkey,
     pub voting_quorum: u64,
     pub voting_window: i64,
+    pub vault_share_bps: u64,
+}
+
+#[event]
+pub struct VaultShareChangeProposed {
+    pub creator_pool: Pubkey,
+    pub current_vault_share_bps: u64,
+    pub pending_vault_share_bps: u64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct VaultShareUpdated {
+    pub creator_pool: Pubkey,
+    pub previous_vault_share_bps: u64,
+    pub vault_share_bps: u64,
+}
+
+#[event]
+pub struct CreatorHeartbeat {
+    pub creator: Pubkey,
+    pub creator_pool: Pubkey,
+    pub last_heartbeat: i64,
+}
+
+#[event]
+pub struct InactivityRefundTriggered {
+    pub creator_pool: Pubkey,
+    pub triggered_by: Pubkey,
+    pub last_heartbeat: i64,
 }
 
 #[event]
@@ -25,6 +85,8 @@ pub struct ClaimFiled {
     pub pool_amount: u64,
     pub evidence_ipfs_hash: String,
     pub voting_ends_at: i64,
+    pub eligible_supply: u64,
+    pub filing_bond: u64,
 }
 
 #[event]
@@ -35,6 +97,15 @@ pub struct VoteCast {
     pub voted_at: i64,
 }
 
+#[event]
+pub struct VotingExtended {
+    pub claim: Pubkey,
+    pub creator_pool: Pubkey,
+    pub previous_ends_at: i64,
+    pub voting_ends_at: i64,
+    pub vote_extension_total: i64,
+}
+
 #[event]
 pub struct ClaimFinalized {
     pub claim: Pubkey,
@@ -46,6 +117,21 @@ pub struct ClaimFinalized {
     pub status: String,
 }
 
+#[event]
+pub struct CrankBountyPaid {
+    pub claim: Pubkey,
+    pub cranker: Pubkey,
+    pub bounty: u64,
+}
+
+#[event]
+pub struct ClaimBondSettled {
+    pub claim: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub forfeited: bool,
+}
+
 #[event]
 pub struct ClaimCanceled {
     pub claim: Pubkey,
@@ -69,6 +155,62 @@ pub struct RefundDistributed {
     pub nft_holders_count: u64,
 }
 
+#[event]
+pub struct MisconductProposalOpened {
+    pub proposal: Pubkey,
+    pub creator_pool: Pubkey,
+    pub proposer: Pubkey,
+    pub evidence_ipfs_hash: String,
+}
+
+#[event]
+pub struct MisconductProposalCosigned {
+    pub proposal: Pubkey,
+    pub cosigner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub cosign_count: u64,
+}
+
+#[event]
+pub struct MisconductVotingStarted {
+    pub proposal: Pubkey,
+    pub creator_pool: Pubkey,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct MisconductVoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub nft_mint: Pubkey,
+    pub vote_choice: bool,
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct MisconductProposalFinalized {
+    pub proposal: Pubkey,
+    pub creator_pool: Pubkey,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub passed: bool,
+}
+
+#[event]
+pub struct PoolRefundStarted {
+    pub creator_pool: Pubkey,
+    pub refundable_lamports: u64,
+    pub pass_supply: u64,
+}
+
+#[event]
+pub struct PoolRefundWithdrawn {
+    pub creator_pool: Pubkey,
+    pub holder: Pubkey,
+    pub nft_mint: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct NftOwnershipVerified {
     pub voter: Pubkey,
@@ -85,3 +227,175 @@ pub struct NftSaleRevenueDistributed {
     pub creator_amount: u64,
     pub platform_fee: u64,
 }
+
+#[event]
+pub struct TreasuryInitialized {
+    pub treasury: Pubkey,
+    pub factory: Pubkey,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+}
+
+#[event]
+pub struct CreatorCollectionCreated {
+    pub creator: Pubkey,
+    pub creator_collection: Pubkey,
+    pub collection_mint: Pubkey,
+    pub base_price: u64,
+    pub price_curve: PriceCurve,
+    pub max_supply: u64,
+    pub max_per_wallet: u64,
+    pub referral_bps: u64,
+    pub royalty_bps: u64,
+    pub soulbound: bool,
+    pub exit_fee_bps: u64,
+    pub pass_duration: i64,
+    pub renewal_price: u64,
+}
+
+#[event]
+pub struct PresaleConfigured {
+    pub creator_collection: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub presale_price: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct PassPurchased {
+    pub buyer: Pubkey,
+    pub creator: Pubkey,
+    pub creator_collection: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub creator_pool_amount: u64,
+    pub creator_amount: u64,
+    pub platform_fee: u64,
+    pub total_supply: u64,
+    pub presale: bool,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+    pub referrer_stats: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewarded {
+    pub referrer: Pubkey,
+    pub buyer: Pubkey,
+    pub creator_collection: Pubkey,
+    pub amount: u64,
+    pub referral_count: u64,
+    pub total_earned: u64,
+}
+
+#[event]
+pub struct PassListed {
+    pub seller: Pubkey,
+    pub nft_mint: Pubkey,
+    pub listing: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+pub struct PassListingCancelled {
+    pub seller: Pubkey,
+    pub nft_mint: Pubkey,
+    pub listing: Pubkey,
+}
+
+#[event]
+pub struct PassSold {
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub creator_collection: Pubkey,
+    pub price: u64,
+    pub royalty: u64,
+    pub creator_pool_amount: u64,
+    pub creator_amount: u64,
+    pub seller_amount: u64,
+}
+
+#[event]
+pub struct PassTransferApproved {
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    pub nft_mint: Pubkey,
+    pub creator_collection: Pubkey,
+    pub royalty: u64,
+    pub creator_pool_amount: u64,
+    pub creator_amount: u64,
+}
+
+#[event]
+pub struct PassOwnershipSynced {
+    pub nft_mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub acquired_at: i64,
+}
+
+#[event]
+pub struct CompressedTreeSet {
+    pub creator_collection: Pubkey,
+    pub merkle_tree: Pubkey,
+}
+
+#[event]
+pub struct PassRegistered {
+    pub creator_collection: Pubkey,
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub acquired_at: i64,
+}
+
+#[event]
+pub struct CoreCollectionSet {
+    pub creator_collection: Pubkey,
+    pub core_collection: Pubkey,
+}
+
+#[event]
+pub struct PassRedeemed {
+    pub holder: Pubkey,
+    pub nft_mint: Pubkey,
+    pub creator_collection: Pubkey,
+    pub payout: u64,
+    pub exit_fee: u64,
+    pub total_supply: u64,
+}
+
+#[event]
+pub struct PassRenewed {
+    pub payer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub creator_collection: Pubkey,
+    pub price: u64,
+    pub creator_pool_amount: u64,
+    pub creator_amount: u64,
+    pub platform_fee: u64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct PassRevoked {
+    pub creator: Pubkey,
+    pub holder: Pubkey,
+    pub nft_mint: Pubkey,
+    pub creator_collection: Pubkey,
+    pub refund: u64,
+    pub reason_hash: [u8; 32],
+    pub revoked_at: i64,
+}
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::{AccountInfo, Rent, Result, SolanaSysvar};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+pub const DEFAULT_VAULT_SHARE_BPS: u64 = 7_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaleSplit {
+    pub platform_fee: u64,
+    pub creator_amount: u64,
+    pub vault_amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redemption {
+    pub payout: u64,
+    pub exit_fee: u64,
+}
+
+// Rounds down, so callers decide where the remainder goes
+pub fn bps_of(amount: u64, bps: u64) -> Option<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(share).ok()
+}
+
+// Splits a sale between the platform, the creator and the vault. Whatever integer
+// rounding leaves over is assigned to the vault so fans are never shorted.
+pub fn split_sale(total_amount: u64, platform_fee_bps: u64, vault_bps: u64) -> Option<SaleSplit> {
+    let reserved_bps = platform_fee_bps.checked_add(vault_bps)?;
+    if reserved_bps > BPS_DENOMINATOR {
+        return None;
+    }
+
+    let platform_fee = bps_of(total_amount, platform_fee_bps)?;
+    let creator_amount = bps_of(total_amount, BPS_DENOMINATOR - reserved_bps)?;
+    let vault_amount = total_amount
+        .checked_sub(platform_fee)?
+        .checked_sub(creator_amount)?;
+
+    Some(SaleSplit {
+        platform_fee,
+        creator_amount,
+        vault_amount,
+    })
+}
+
+// What a vault can pay out while keeping its rent-exempt minimum
+pub fn unreserved_lamports(vault_lamports: u64, rent_minimum: u64) -> u64 {
+    vault_lamports.saturating_sub(rent_minimum)
+}
+
+pub fn unreserved_vault_lamports(sol_vault: &AccountInfo) -> Result<u64> {
+    Ok(unreserved_lamports(
+        sol_vault.lamports(),
+        Rent::get()?.minimum_balance(0),
+    ))
+}
+
+// One pass's pro-rata share of the vault, less an exit fee that stays behind for
+// the remaining holders. The division remainder also stays in the vault.
+pub fn split_redemption(
+    vault_lamports: u64,
+    pass_supply: u64,
+    exit_fee_bps: u64,
+) -> Option<Redemption> {
+    if exit_fee_bps > BPS_DENOMINATOR {
+        return None;
+    }
+
+    let share = vault_lamports.checked_div(pass_supply)?;
+    let exit_fee = bps_of(share, exit_fee_bps)?;
+
+    Some(Redemption {
+        payout: share.checked_sub(exit_fee)?,
+        exit_fee,
+    })
+}
+
+// The filing bond is the larger of the factory's fixed amount and its share of the pool
+pub fn filing_bond(pool_balance: u64, fixed_amount: u64, bond_bps: u64) -> Option<u64> {
+    Some(bps_of(pool_balance, bond_bps)?.max(fixed_amount))
+}
+
+// A revoked holder gets the larger of their pro-rata share and what their purchase put
+// into the vault, but never so much that the remaining passes' share goes down
+pub fn revocation_refund(vault_lamports: u64, pass_supply: u64, contribution: u64) -> Option<u64> {
+    let share = split_redemption(vault_lamports, pass_supply, 0)?.payout;
+    let kept_for_others = share.checked_mul(pass_supply.checked_sub(1)?)?;
+    let max_refund = vault_lamports.checked_sub(kept_for_others)?;
+    Some(share.max(contribution).min(max_refund))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_evenly_divisible_amounts_exactly() {
+        let split = split_sale(1_000_000, 500, 7_000).unwrap();
+        assert_eq!(
+            split,
+            SaleSplit {
+                platform_fee: 50_000,
+                creator_amount: 250_000,
+                vault_amount: 700_000,
+            }
+        );
+    }
+
+    #[test]
+    fn assigns_rounding_remainder_to_vault() {
+        let split = split_sale(999, 333, 3_333).unwrap();
+        assert_eq!(split.platform_fee, 33);
+        assert_eq!(split.creator_amount, 632);
+        assert_eq!(split.vault_amount, 334);
+        assert_eq!(
+            split.platform_fee + split.creator_amount + split.vault_amount,
+            999
+        );
+    }
+
+    #[test]
+    fn never_loses_lamports() {
+        for total in [0, 1, 7, 101, 9_999, 123_456_789, u64::MAX] {
+            let split = split_sale(total, 250, 7_000).unwrap();
+            assert_eq!(
+                split.platform_fee as u128
+                    + split.creator_amount as u128
+                    + split.vault_amount as u128,
+                total as u128
+            );
+        }
+    }
+
+    #[test]
+    fn handles_amounts_that_overflow_u64_multiplication() {
+        let split = split_sale(u64::MAX, BPS_DENOMINATOR, 0).unwrap();
+        assert_eq!(split.platform_fee, u64::MAX);
+        assert_eq!(split.creator_amount, 0);
+        assert_eq!(split.vault_amount, 0);
+    }
+
+    #[test]
+    fn rejects_shares_above_one_hundred_percent() {
+        assert_eq!(split_sale(1_000, 3_001, 7_000), None);
+        assert_eq!(split_sale(1_000, u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(19, 5_000), Some(9));
+        assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR), Some(u64::MAX));
+        assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR + 1), None);
+    }
+
+    #[test]
+    fn redemption_pays_share_less_exit_fee() {
+        let redemption = split_redemption(1_000_003, 4, 1_000).unwrap();
+        assert_eq!(
+            redemption,
+            Redemption {
+                payout: 225_000,
+                exit_fee: 25_000,
+            }
+        );
+    }
+
+    #[test]
+    fn redemption_rejects_empty_supply_and_oversized_fee() {
+        assert_eq!(split_redemption(1_000, 0, 0), None);
+        assert_eq!(split_redemption(1_000, 1, BPS_DENOMINATOR + 1), None);
+    }
+
+    #[test]
+    fn unreserved_lamports_keep_the_rent_minimum_back() {
+        assert_eq!(unreserved_lamports(1_890_880, 890_880), 1_000_000);
+        assert_eq!(unreserved_lamports(890_880, 890_880), 0);
+        assert_eq!(unreserved_lamports(0, 890_880), 0);
+    }
+
+    #[test]
+    fn filing_bond_takes_the_larger_of_fixed_and_percentage() {
+        assert_eq!(filing_bond(1_000_000, 50_000, 1_000), Some(100_000));
+        assert_eq!(filing_bond(1_000_000, 150_000, 1_000), Some(150_000));
+        assert_eq!(filing_bond(0, 0, 1_000), Some(0));
+    }
+
+    #[test]
+    fn revocation_refund_leaves_the_other_holders_share_unchanged() {
+        for (vault, supply, contribution) in [
+            (1_000_000, 4, 100_000),
+            (1_000_000, 4, 600_000),
+            (1_000_003, 4, 400_000),
+        ] {
+            let refund = revocation_refund(vault, supply, contribution).unwrap();
+            let share_before = split_redemption(vault, supply, 0).unwrap().payout;
+            let share_after = split_redemption(vault - refund, supply - 1, 0)
+                .unwrap()
+                .payout;
+            assert_eq!(share_after, share_before);
+        }
+    }
+
+    #[test]
+    fn revocation_refund_covers_the_contribution_only_out_of_the_remainder() {
+        assert_eq!(revocation_refund(1_000_000, 4, 100_000), Some(250_000));
+        assert_eq!(revocation_refund(1_000_000, 4, 600_000), Some(250_000));
+        assert_eq!(revocation_refund(1_000_003, 4, 400_000), Some(250_003));
+        assert_eq!(revocation_refund(1_000_003, 4, 250_001), Some(250_001));
+    }
+
+    #[test]
+    fn sole_holder_revocation_refunds_the_whole_vault() {
+        assert_eq!(revocation_refund(700_000, 1, 0), Some(700_000));
+        assert_eq!(revocation_refund(700_000, 1, u64::MAX), Some(700_000));
+        assert_eq!(revocation_refund(700_000, 0, 0), None);
+    }
+}
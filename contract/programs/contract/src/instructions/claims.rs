@@ -1,5 +1,9 @@
-use crate::events::{ClaimCanceled, ClaimFiled, ClaimFinalized, PayoutSent, RefundDistributed};
-use crate::state::{Claim, CreatorPool, Factory};
+use crate::events::{
+    ClaimBondSettled, ClaimCanceled, ClaimFiled, ClaimFinalized, CrankBountyPaid, PayoutSent,
+    RefundDistributed,
+};
+use crate::fees::filing_bond;
+use crate::state::{Claim, ClaimBond, CreatorCollection, CreatorPool, Factory, PoolStatus};
 use anchor_lang::prelude::*;
 // No longer using SPL tokens
 
@@ -15,7 +19,11 @@ pub struct FileClaim<'info> {
     )]
     pub claim: Account<'info, Claim>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::InvalidCreatorPoolAddress,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
     pub creator_pool: Account<'info, CreatorPool>,
 
     #[account(mut)]
@@ -25,12 +33,30 @@ pub struct FileClaim<'info> {
     #[account(mut)]
     pub creator_usdc_account: AccountInfo<'info>,
 
-    /// CHECK: This is a regular account that can hold native SOL
-    #[account(mut)]
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_pool.creator.as_ref()],
+        bump
+    )]
     pub creator_pool_vault: AccountInfo<'info>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = ClaimBond::LEN,
+        seeds = [b"claim_bond", claim.key().as_ref()],
+        bump
+    )]
+    pub claim_bond: Account<'info, ClaimBond>,
+
     pub factory: Account<'info, Factory>,
 
+    #[account(
+        constraint = creator_collection.creator == creator_pool.creator @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -55,14 +81,36 @@ pub struct FinalizeClaim<'info> {
         mut,
         has_one = creator_pool,
         constraint = claim.status == crate::state::ClaimStatus::Voting @ ErrorCode::InvalidClaimStatus,
-        constraint = Clock::get()?.unix_timestamp >= claim.voting_ends_at @ ErrorCode::VotingStillActive
+        constraint = (Clock::get()?.unix_timestamp >= claim.voting_ends_at || claim.is_outcome_decided()) @ ErrorCode::VotingStillActive
     )]
     pub claim: Account<'info, Claim>,
 
     #[account(mut)]
     pub creator_pool: Account<'info, CreatorPool>,
 
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_pool.creator.as_ref()],
+        bump
+    )]
+    pub creator_pool_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"claim_bond", claim.key().as_ref()],
+        bump = claim_bond.bump
+    )]
+    pub claim_bond: Account<'info, ClaimBond>,
+
+    /// CHECK: Receives the returned filing bond
+    #[account(mut, address = claim.creator @ ErrorCode::InvalidCreatorAccount)]
+    pub creator: AccountInfo<'info>,
+
     pub factory: Account<'info, Factory>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -74,7 +122,10 @@ pub struct PayoutClaim<'info> {
     )]
     pub claim: Account<'info, Claim>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
     pub creator_pool: Account<'info, CreatorPool>,
 
     #[account(mut)]
@@ -114,7 +165,7 @@ pub struct FinalizeClaimWithDistribution<'info> {
         mut,
         has_one = creator_pool,
         constraint = (claim.status == crate::state::ClaimStatus::Voting || claim.status == crate::state::ClaimStatus::Pending) @ ErrorCode::InvalidClaimStatus,
-        constraint = Clock::get()?.unix_timestamp >= claim.voting_ends_at @ ErrorCode::VotingStillActive
+        constraint = (Clock::get()?.unix_timestamp >= claim.voting_ends_at || claim.is_outcome_decided()) @ ErrorCode::VotingStillActive
     )]
     pub claim: Account<'info, Claim>,
 
@@ -130,12 +181,19 @@ pub struct FinalizeClaimWithDistribution<'info> {
     )]
     pub creator_pool_vault: AccountInfo<'info>,
 
-    /// CHECK: This is a regular account that can hold native SOL
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"claim_bond", claim.key().as_ref()],
+        bump = claim_bond.bump
+    )]
+    pub claim_bond: Account<'info, ClaimBond>,
+
+    /// CHECK: Payouts always go to the claim's creator
+    #[account(mut, address = claim.creator @ ErrorCode::InvalidCreatorAccount)]
     pub creator_usdc_account: AccountInfo<'info>,
 
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub cranker: Signer<'info>,
 
     pub factory: Account<'info, Factory>,
 
@@ -183,6 +241,64 @@ pub enum ErrorCode {
     InvalidCreatorPoolAddress,
     #[msg("Invalid vault account provided")]
     InvalidVaultAccount,
+    #[msg("Creator collection does not belong to this pool's creator")]
+    InvalidCreatorCollection,
+    #[msg("Creator account does not match the claim")]
+    InvalidCreatorAccount,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+}
+
+// Frees the pool's open-claim slot when a claim leaves voting
+fn release_active_claim(claim: &Claim, creator_pool: &mut CreatorPool) {
+    // Claims filed before active_claims existed were never counted
+    if claim.status == crate::state::ClaimStatus::Voting {
+        creator_pool.active_claims = creator_pool.active_claims.saturating_sub(1);
+    }
+}
+
+// Returns the filing bond to the creator, or forfeits it to the refund pool on rejection
+fn settle_claim_bond<'info>(
+    claim_bond: &Account<'info, ClaimBond>,
+    creator: &AccountInfo<'info>,
+    refund_pool: &AccountInfo<'info>,
+    forfeited: bool,
+) -> Result<()> {
+    if forfeited && claim_bond.amount > 0 {
+        claim_bond.sub_lamports(claim_bond.amount)?;
+        refund_pool.add_lamports(claim_bond.amount)?;
+    }
+
+    emit!(ClaimBondSettled {
+        claim: claim_bond.claim,
+        creator: claim_bond.creator,
+        amount: claim_bond.amount,
+        forfeited,
+    });
+
+    claim_bond.close(creator.clone())
+}
+
+// Pays the finalizer the bounty the creator escrowed in the claim account at filing
+fn pay_crank_bounty<'info>(
+    claim: &mut Account<'info, Claim>,
+    cranker: &Signer<'info>,
+) -> Result<()> {
+    let bounty = claim.take_crank_bounty();
+    if bounty == 0 {
+        return Ok(());
+    }
+
+    claim.sub_lamports(bounty)?;
+    cranker.add_lamports(bounty)?;
+
+    emit!(CrankBountyPaid {
+        claim: claim.key(),
+        cranker: cranker.key(),
+        bounty,
+    });
+
+    Ok(())
 }
 
 pub fn file_claim(
@@ -227,10 +343,70 @@ pub fn file_claim(
     claim.voting_ends_at = Clock::get()?.unix_timestamp + creator_pool.voting_window;
     claim.created_at = Clock::get()?.unix_timestamp;
     claim.bump = ctx.bumps.claim;
+    claim.vote_extension_total = 0;
+    // The extension rule is fixed at filing so the creator can't retune it mid-vote
+    claim.vote_extension_window = creator_pool.vote_extension_window;
+    claim.vote_extension_duration = creator_pool.vote_extension_duration;
+    claim.max_vote_extension = creator_pool.max_vote_extension;
+
+    // Snapshot the pass supply so the claim can be finalized early once decided.
+    // Votes only count from passes acquired by now, so the snapshot bounds them.
+    claim.eligible_supply = ctx
+        .accounts
+        .creator_collection
+        .pass_supply()
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Escrow the filing bond, the larger of the fixed amount and the share of the pool
+    let filing_bond = filing_bond(
+        pool_balance,
+        ctx.accounts.factory.filing_bond_amount,
+        ctx.accounts.factory.filing_bond_bps,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+
+    let claim_bond = &mut ctx.accounts.claim_bond;
+    claim_bond.claim = claim.key();
+    claim_bond.creator = claim.creator;
+    claim_bond.amount = filing_bond;
+    claim_bond.bump = ctx.bumps.claim_bond;
+
+    if filing_bond > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: claim_bond.to_account_info(),
+                },
+            ),
+            filing_bond,
+        )?;
+    }
+
+    // Reserve the finalization bounty inside the claim account
+    claim.crank_bounty = ctx.accounts.factory.crank_bounty;
+    if claim.crank_bounty > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: claim.to_account_info(),
+                },
+            ),
+            claim.crank_bounty,
+        )?;
+    }
 
     // Update creator_pool.claim_count to be the maximum of current count and provided count + 1
     let new_claim_count = std::cmp::max(creator_pool.claim_count, (claim_count + 1).into());
     creator_pool.claim_count = new_claim_count;
+    creator_pool.last_heartbeat = claim.created_at;
+    creator_pool.active_claims = creator_pool
+        .active_claims
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     emit!(ClaimFiled {
         claim: claim.key(),
@@ -239,6 +415,8 @@ pub fn file_claim(
         pool_amount: claim.pool_amount_at_claim,
         evidence_ipfs_hash: claim.evidence_ipfs_hash.clone(),
         voting_ends_at: claim.voting_ends_at,
+        eligible_supply: claim.eligible_supply,
+        filing_bond,
     });
 
     Ok(())
@@ -265,23 +443,26 @@ pub fn cancel_claim(ctx: Context<CancelClaim>) -> Result<()> {
 
 pub fn finalize_claim(ctx: Context<FinalizeClaim>) -> Result<()> {
     let claim = &mut ctx.accounts.claim;
-    let creator_pool = &ctx.accounts.creator_pool;
-
-    let total_votes = claim
-        .yes_votes
-        .checked_add(claim.no_votes)
-        .ok_or(ErrorCode::MathOverflow)?;
+    let creator_pool = &mut ctx.accounts.creator_pool;
 
-    require!(
-        total_votes >= creator_pool.voting_quorum,
-        ErrorCode::InsufficientVotes
-    );
+    release_active_claim(claim, creator_pool);
 
-    if claim.yes_votes > claim.no_votes {
-        claim.status = crate::state::ClaimStatus::Approved;
-    } else {
-        claim.status = crate::state::ClaimStatus::Rejected;
+    let outcome = claim.voted_outcome(creator_pool.voting_quorum);
+    if outcome == crate::state::ClaimStatus::Expired {
+        // Without quorum the claim can only expire, and only once voting has ended
+        require!(
+            Clock::get()?.unix_timestamp >= claim.voting_ends_at,
+            ErrorCode::InsufficientVotes
+        );
     }
+    claim.status = outcome;
+
+    settle_claim_bond(
+        &ctx.accounts.claim_bond,
+        &ctx.accounts.creator,
+        &ctx.accounts.creator_pool_vault,
+        claim.status == crate::state::ClaimStatus::Rejected,
+    )?;
 
     emit!(ClaimFinalized {
         claim: claim.key(),
@@ -293,10 +474,13 @@ pub fn finalize_claim(ctx: Context<FinalizeClaim>) -> Result<()> {
         status: match claim.status {
             crate::state::ClaimStatus::Approved => "Approved".to_string(),
             crate::state::ClaimStatus::Rejected => "Rejected".to_string(),
+            crate::state::ClaimStatus::Expired => "Expired".to_string(),
             _ => "Unknown".to_string(),
         },
     });
 
+    pay_crank_bounty(claim, &ctx.accounts.cranker)?;
+
     Ok(())
 }
 
@@ -364,21 +548,55 @@ pub fn finalize_claim_with_distribution(ctx: Context<FinalizeClaimWithDistributi
     let claim = &mut ctx.accounts.claim;
     let creator_pool = &mut ctx.accounts.creator_pool;
 
-    let total_votes = claim
-        .yes_votes
-        .checked_add(claim.no_votes)
-        .ok_or(ErrorCode::MathOverflow)?;
+    release_active_claim(claim, creator_pool);
+
+    let outcome = claim.voted_outcome(creator_pool.voting_quorum);
+
+    // Once a pool refund has snapshotted the vault, open claims expire without a payout
+    let pool_active = creator_pool.status == PoolStatus::Active;
+    if !pool_active || outcome == crate::state::ClaimStatus::Expired {
+        // Without quorum the claim can only expire, and only once voting has ended
+        if pool_active {
+            require!(
+                Clock::get()?.unix_timestamp >= claim.voting_ends_at,
+                ErrorCode::InsufficientVotes
+            );
+        }
+        claim.status = crate::state::ClaimStatus::Expired;
+
+        settle_claim_bond(
+            &ctx.accounts.claim_bond,
+            &ctx.accounts.creator_usdc_account,
+            &ctx.accounts.creator_pool_vault,
+            false,
+        )?;
 
-    require!(
-        total_votes >= creator_pool.voting_quorum,
-        ErrorCode::InsufficientVotes
-    );
+        emit!(ClaimFinalized {
+            claim: claim.key(),
+            creator: claim.creator,
+            creator_pool: claim.creator_pool,
+            pool_amount: claim.pool_amount_at_claim,
+            yes_votes: claim.yes_votes,
+            no_votes: claim.no_votes,
+            status: "Expired".to_string(),
+        });
+
+        return pay_crank_bounty(claim, &ctx.accounts.cranker);
+    }
+
+    // A rejected claim's bond joins the funds left in the vault for holders
+    settle_claim_bond(
+        &ctx.accounts.claim_bond,
+        &ctx.accounts.creator_usdc_account,
+        &ctx.accounts.creator_pool_vault,
+        outcome == crate::state::ClaimStatus::Rejected,
+    )?;
 
     // For native SOL vault, get the lamports
     let payout_amount = ctx.accounts.creator_pool_vault.lamports();
     require!(payout_amount > 0, ErrorCode::InsufficientFunds);
 
-    if claim.yes_votes > claim.no_votes {
+    if outcome == crate::state::ClaimStatus::Approved {
         // Claim approved - transfer funds from vault to creator
         claim.status = crate::state::ClaimStatus::Approved;
 
@@ -438,6 +656,8 @@ pub fn finalize_claim_with_distribution(ctx: Context<FinalizeClaimWithDistributi
         },
     });
 
+    pay_crank_bounty(claim, &ctx.accounts.cranker)?;
+
     Ok(())
 }
 
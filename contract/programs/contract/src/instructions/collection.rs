@@ -0,0 +1,173 @@
+use crate::events::{CreatorCollectionCreated, PresaleConfigured};
+use crate::fees::BPS_DENOMINATOR;
+use crate::state::{CreatorCollection, CreatorPool, PriceCurve};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct CreateCollection<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = CreatorCollection::LEN,
+        seeds = [b"creator_collection", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    #[account(
+        seeds = [b"creator_pool", creator.key().as_ref()],
+        bump = creator_pool.bump,
+        has_one = creator
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    pub collection_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Metadata account of the collection NFT, recorded for off-chain lookups
+    pub collection_metadata: AccountInfo<'info>,
+
+    /// CHECK: Master edition of the collection NFT, recorded for off-chain lookups
+    pub collection_master_edition: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPresale<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator.key().as_ref()],
+        bump = creator_collection.bump,
+        has_one = creator
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateCollectionParams {
+    pub base_price: u64,
+    pub price_curve: PriceCurve,
+    pub max_supply: u64,
+    pub max_per_wallet: u64,
+    pub referral_bps: u64,
+    pub royalty_bps: u64,
+    pub soulbound: bool,
+    pub exit_fee_bps: u64,
+    pub pass_duration: i64,
+    pub renewal_price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetPresaleParams {
+    pub merkle_root: [u8; 32],
+    pub presale_price: u64,
+    pub starts_at: i64,
+    pub ends_at: i64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Exponential growth cannot exceed 100% per pass")]
+    InvalidPriceCurve,
+    #[msg("Presale window must end after it starts")]
+    InvalidPresaleWindow,
+    #[msg("Referral share cannot exceed the creator's share")]
+    InvalidReferralBps,
+    #[msg("Royalty cannot exceed the resale price")]
+    InvalidRoyaltyBps,
+    #[msg("Exit fee cannot exceed the redeemed share")]
+    InvalidExitFeeBps,
+    #[msg("Pass duration cannot be negative")]
+    InvalidPassDuration,
+}
+
+pub fn create_collection(
+    ctx: Context<CreateCollection>,
+    params: CreateCollectionParams,
+) -> Result<()> {
+    if let PriceCurve::Exponential { growth_bps } = params.price_curve {
+        require!(growth_bps <= BPS_DENOMINATOR, ErrorCode::InvalidPriceCurve);
+    }
+    require!(
+        params.referral_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidReferralBps
+    );
+    require!(
+        params.royalty_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidRoyaltyBps
+    );
+    require!(
+        params.exit_fee_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidExitFeeBps
+    );
+    require!(params.pass_duration >= 0, ErrorCode::InvalidPassDuration);
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.creator = ctx.accounts.creator.key();
+    creator_collection.collection_mint = ctx.accounts.collection_mint.key();
+    creator_collection.collection_metadata = ctx.accounts.collection_metadata.key();
+    creator_collection.collection_master_edition = ctx.accounts.collection_master_edition.key();
+    creator_collection.total_supply = 0;
+    creator_collection.created_at = Clock::get()?.unix_timestamp;
+    creator_collection.bump = ctx.bumps.creator_collection;
+    creator_collection.base_price = params.base_price;
+    creator_collection.price_curve = params.price_curve.clone();
+    creator_collection.max_supply = params.max_supply;
+    creator_collection.max_per_wallet = params.max_per_wallet;
+    creator_collection.referral_bps = params.referral_bps;
+    creator_collection.royalty_bps = params.royalty_bps;
+    creator_collection.soulbound = params.soulbound;
+    creator_collection.exit_fee_bps = params.exit_fee_bps;
+    creator_collection.pass_duration = params.pass_duration;
+    creator_collection.renewal_price = params.renewal_price;
+
+    emit!(CreatorCollectionCreated {
+        creator: creator_collection.creator,
+        creator_collection: creator_collection.key(),
+        collection_mint: creator_collection.collection_mint,
+        base_price: params.base_price,
+        price_curve: params.price_curve,
+        max_supply: params.max_supply,
+        max_per_wallet: params.max_per_wallet,
+        referral_bps: params.referral_bps,
+        royalty_bps: params.royalty_bps,
+        soulbound: params.soulbound,
+        exit_fee_bps: params.exit_fee_bps,
+        pass_duration: params.pass_duration,
+        renewal_price: params.renewal_price,
+    });
+
+    Ok(())
+}
+
+// A zero root clears the presale and opens the public sale
+pub fn set_presale(ctx: Context<SetPresale>, params: SetPresaleParams) -> Result<()> {
+    if params.merkle_root != [0; 32] {
+        require!(
+            params.ends_at > params.starts_at,
+            ErrorCode::InvalidPresaleWindow
+        );
+    }
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.presale_merkle_root = params.merkle_root;
+    creator_collection.presale_price = params.presale_price;
+    creator_collection.presale_starts_at = params.starts_at;
+    creator_collection.presale_ends_at = params.ends_at;
+
+    emit!(PresaleConfigured {
+        creator_collection: creator_collection.key(),
+        merkle_root: params.merkle_root,
+        presale_price: params.presale_price,
+        starts_at: params.starts_at,
+        ends_at: params.ends_at,
+    });
+
+    Ok(())
+}
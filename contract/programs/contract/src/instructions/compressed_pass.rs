@@ -0,0 +1,268 @@
+use crate::events::CompressedTreeSet;
+use crate::instructions::fanpass::FanPassInfo;
+use crate::instructions::pass_ownership::{
+    fan_pass_info, register_pass, CompressedPass, PassOwnership,
+};
+use crate::state::{CreatorCollection, RegisteredPass};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::pubkey;
+use solana_keccak_hasher::hashv;
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfxf1JFw8iyQ");
+
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+// Anchor discriminator of account-compression's verify_leaf instruction
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+// Bubblegum LeafSchema::V1 version byte
+const LEAF_SCHEMA_V1: u8 = 1;
+
+// Byte range of `tree_creator` in Bubblegum's TreeConfig account
+const TREE_CREATOR_RANGE: std::ops::Range<usize> = 8..40;
+
+// Offset of the `is_public` flag in Bubblegum's TreeConfig account
+const TREE_IS_PUBLIC_OFFSET: usize = 88;
+
+// Everything needed to rebuild a Bubblegum leaf; proof nodes are passed as remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedPassProof {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub delegate: Pubkey,
+    pub nonce: u64,
+    pub index: u32,
+}
+
+#[derive(Accounts)]
+pub struct SetCompressedTree<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator.key().as_ref()],
+        bump = creator_collection.bump,
+        has_one = creator
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Concurrent Merkle tree holding the creator's compressed passes
+    #[account(owner = ACCOUNT_COMPRESSION_PROGRAM_ID @ ErrorCode::InvalidMerkleTree)]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: Bubblegum tree config, parsed to confirm the creator owns the tree
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = BUBBLEGUM_PROGRAM_ID,
+        bump,
+        owner = BUBBLEGUM_PROGRAM_ID @ ErrorCode::InvalidMerkleTree
+    )]
+    pub tree_config: AccountInfo<'info>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCompressedFanPass<'info> {
+    pub fan: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_collection.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Must be the tree registered on the creator collection
+    #[account(address = creator_collection.merkle_tree @ ErrorCode::InvalidMerkleTree)]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof: CompressedPassProof)]
+pub struct RegisterCompressedPass<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator_collection.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Must be the tree registered on the creator collection
+    #[account(address = creator_collection.merkle_tree @ ErrorCode::InvalidMerkleTree)]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = fan,
+        space = RegisteredPass::LEN,
+        seeds = [
+            b"registered_pass",
+            compressed_asset_id(&merkle_tree.key(), proof.nonce).as_ref()
+        ],
+        bump
+    )]
+    pub registered_pass: Account<'info, RegisteredPass>,
+
+    #[account(mut)]
+    pub fan: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Merkle tree is not a Bubblegum tree owned by this creator")]
+    InvalidMerkleTree,
+    #[msg("Creator collection has no compressed pass tree")]
+    NoCompressedTree,
+    #[msg("Public trees let anyone mint passes")]
+    PublicMerkleTree,
+}
+
+// Bubblegum derives each compressed asset's id from its tree and leaf nonce
+pub fn compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+// Rebuilds the holder's leaf and has account-compression check it against the tree root.
+// Only the tree's creator (or its delegate) can mint into it, so a valid leaf is a member.
+pub fn verify_compressed_pass<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    proof_nodes: &[AccountInfo<'info>],
+    owner: &Pubkey,
+    proof: &CompressedPassProof,
+) -> Result<Pubkey> {
+    require_keys_neq!(
+        merkle_tree.key(),
+        Pubkey::default(),
+        ErrorCode::NoCompressedTree
+    );
+
+    let asset_id = compressed_asset_id(merkle_tree.key, proof.nonce);
+    let leaf = hashv(&[
+        &[LEAF_SCHEMA_V1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        proof.delegate.as_ref(),
+        &proof.nonce.to_le_bytes(),
+        &proof.data_hash,
+        &proof.creator_hash,
+    ])
+    .to_bytes();
+
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&proof.root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&proof.index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(
+        proof_nodes
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend_from_slice(proof_nodes);
+    account_infos.push(compression_program.clone());
+
+    invoke(
+        &Instruction {
+            program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(asset_id)
+}
+
+pub fn set_compressed_tree(ctx: Context<SetCompressedTree>) -> Result<()> {
+    let (tree_creator, is_public) = {
+        let data = ctx.accounts.tree_config.try_borrow_data()?;
+        let bytes = data
+            .get(TREE_CREATOR_RANGE)
+            .ok_or(ErrorCode::InvalidMerkleTree)?;
+        let is_public = data
+            .get(TREE_IS_PUBLIC_OFFSET)
+            .ok_or(ErrorCode::InvalidMerkleTree)?;
+        (
+            Pubkey::try_from(bytes).map_err(|_| ErrorCode::InvalidMerkleTree)?,
+            *is_public != 0,
+        )
+    };
+    require_keys_eq!(
+        tree_creator,
+        ctx.accounts.creator.key(),
+        ErrorCode::InvalidMerkleTree
+    );
+    require!(!is_public, ErrorCode::PublicMerkleTree);
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.merkle_tree = ctx.accounts.merkle_tree.key();
+
+    emit!(CompressedTreeSet {
+        creator_collection: creator_collection.key(),
+        merkle_tree: creator_collection.merkle_tree,
+    });
+
+    Ok(())
+}
+
+pub fn verify_compressed_fan_pass<'info>(
+    ctx: Context<'_, '_, '_, 'info, VerifyCompressedFanPass<'info>>,
+    proof: CompressedPassProof,
+) -> Result<FanPassInfo> {
+    let pass = CompressedPass {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        proof_nodes: ctx.remaining_accounts,
+        proof: &proof,
+    };
+
+    fan_pass_info(
+        &pass,
+        &ctx.accounts.fan.key(),
+        &ctx.accounts.creator_collection,
+    )
+}
+
+// Compressed passes have no mint, so holders register them to get a dated ownership record
+// that claim votes can check against the filing time
+pub fn register_compressed_pass<'info>(
+    ctx: Context<'_, '_, '_, 'info, RegisterCompressedPass<'info>>,
+    proof: CompressedPassProof,
+) -> Result<()> {
+    let fan = ctx.accounts.fan.key();
+    CompressedPass {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        proof_nodes: ctx.remaining_accounts,
+        proof: &proof,
+    }
+    .verify(&fan, &ctx.accounts.creator_collection)?;
+
+    register_pass(
+        &mut ctx.accounts.registered_pass,
+        &mut ctx.accounts.creator_collection,
+        compressed_asset_id(ctx.accounts.merkle_tree.key, proof.nonce),
+        fan,
+        ctx.bumps.registered_pass,
+    )
+}
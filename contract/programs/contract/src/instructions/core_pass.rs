@@ -0,0 +1,176 @@
+use crate::events::CoreCollectionSet;
+use crate::instructions::fanpass::FanPassInfo;
+use crate::instructions::pass_ownership::{fan_pass_info, register_pass, CorePass, PassOwnership};
+use crate::state::{CreatorCollection, RegisteredPass};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+pub const MPL_CORE_PROGRAM_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+// Metaplex Core account `Key` discriminators
+const CORE_KEY_ASSET_V1: u8 = 1;
+const CORE_KEY_COLLECTION_V1: u8 = 5;
+
+// Metaplex Core `UpdateAuthority::Collection` variant
+const UPDATE_AUTHORITY_COLLECTION: u8 = 2;
+
+#[derive(Accounts)]
+pub struct SetCoreCollection<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator.key().as_ref()],
+        bump = creator_collection.bump,
+        has_one = creator
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Metaplex Core collection, parsed to confirm the creator controls it
+    #[account(owner = MPL_CORE_PROGRAM_ID @ ErrorCode::InvalidCoreCollection)]
+    pub core_collection: AccountInfo<'info>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCoreFanPass<'info> {
+    pub fan: Signer<'info>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_collection.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Metaplex Core asset, parsed by CorePass
+    pub asset: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterCorePass<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator_collection.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Metaplex Core asset, parsed by CorePass
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = fan,
+        space = RegisteredPass::LEN,
+        seeds = [b"registered_pass", asset.key().as_ref()],
+        bump
+    )]
+    pub registered_pass: Account<'info, RegisteredPass>,
+
+    #[account(mut)]
+    pub fan: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Account is not a Metaplex Core collection controlled by this creator")]
+    InvalidCoreCollection,
+    #[msg("Account is not a Metaplex Core asset")]
+    InvalidCoreAsset,
+    #[msg("Creator collection has no Core collection")]
+    NoCoreCollection,
+    #[msg("Core asset is not owned by this wallet")]
+    NotAssetOwner,
+    #[msg("Core asset does not belong to the creator's Core collection")]
+    AssetNotInCollection,
+}
+
+// The fixed-size prefix of a Core AssetV1: key, owner, then the update authority enum
+pub struct CoreAssetHeader {
+    pub owner: Pubkey,
+    pub collection: Option<Pubkey>,
+}
+
+pub fn read_core_asset(asset: &AccountInfo) -> Result<CoreAssetHeader> {
+    require_keys_eq!(
+        *asset.owner,
+        MPL_CORE_PROGRAM_ID,
+        ErrorCode::InvalidCoreAsset
+    );
+
+    let data = asset.try_borrow_data()?;
+    require!(
+        data.first() == Some(&CORE_KEY_ASSET_V1),
+        ErrorCode::InvalidCoreAsset
+    );
+    let owner = data.get(1..33).ok_or(ErrorCode::InvalidCoreAsset)?;
+    let owner = Pubkey::try_from(owner).map_err(|_| ErrorCode::InvalidCoreAsset)?;
+
+    let collection = match data.get(33) {
+        Some(&UPDATE_AUTHORITY_COLLECTION) => {
+            let key = data.get(34..66).ok_or(ErrorCode::InvalidCoreAsset)?;
+            Some(Pubkey::try_from(key).map_err(|_| ErrorCode::InvalidCoreAsset)?)
+        }
+        Some(_) => None,
+        None => return err!(ErrorCode::InvalidCoreAsset),
+    };
+
+    Ok(CoreAssetHeader { owner, collection })
+}
+
+pub fn set_core_collection(ctx: Context<SetCoreCollection>) -> Result<()> {
+    let update_authority = {
+        let data = ctx.accounts.core_collection.try_borrow_data()?;
+        require!(
+            data.first() == Some(&CORE_KEY_COLLECTION_V1),
+            ErrorCode::InvalidCoreCollection
+        );
+        let bytes = data.get(1..33).ok_or(ErrorCode::InvalidCoreCollection)?;
+        Pubkey::try_from(bytes).map_err(|_| ErrorCode::InvalidCoreCollection)?
+    };
+    require_keys_eq!(
+        update_authority,
+        ctx.accounts.creator.key(),
+        ErrorCode::InvalidCoreCollection
+    );
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.core_collection = ctx.accounts.core_collection.key();
+
+    emit!(CoreCollectionSet {
+        creator_collection: creator_collection.key(),
+        core_collection: creator_collection.core_collection,
+    });
+
+    Ok(())
+}
+
+pub fn verify_core_fan_pass(ctx: Context<VerifyCoreFanPass>) -> Result<FanPassInfo> {
+    let pass = CorePass {
+        asset: &ctx.accounts.asset,
+    };
+
+    fan_pass_info(
+        &pass,
+        &ctx.accounts.fan.key(),
+        &ctx.accounts.creator_collection,
+    )
+}
+
+// Core transfers happen outside this program, so holders register to date their ownership
+pub fn register_core_pass(ctx: Context<RegisterCorePass>) -> Result<()> {
+    let fan = ctx.accounts.fan.key();
+    CorePass {
+        asset: &ctx.accounts.asset,
+    }
+    .verify(&fan, &ctx.accounts.creator_collection)?;
+
+    register_pass(
+        &mut ctx.accounts.registered_pass,
+        &mut ctx.accounts.creator_collection,
+        ctx.accounts.asset.key(),
+        fan,
+        ctx.bumps.registered_pass,
+    )
+}
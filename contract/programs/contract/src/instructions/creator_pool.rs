@@ -1,5 +1,12 @@
-use crate::events::CreatorPoolCreated;
-use crate::state::{CreatorPool, Factory, PoolStatus};
+use crate::events::{
+    CreatorHeartbeat, CreatorPoolCreated, InactivityRefundTriggered, PoolRefundStarted,
+    PoolRefundWithdrawn, VaultShareChangeProposed, VaultShareUpdated,
+};
+use crate::fees::unreserved_vault_lamports;
+use crate::instructions::fanpass::{require_pass_holder, require_pass_owner};
+use crate::state::{
+    CreatorCollection, CreatorPool, Factory, NftOwnership, PoolRefundReceipt, PoolStatus,
+};
 use anchor_lang::prelude::*;
 // No longer using SPL tokens
 
@@ -58,7 +65,8 @@ pub struct WithdrawFromPool<'info> {
         mut,
         seeds = [b"creator_pool", creator.key().as_ref()],
         bump = creator_pool.bump,
-        constraint = creator_pool.creator == creator.key()
+        constraint = creator_pool.creator == creator.key(),
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
     )]
     pub creator_pool: Account<'info, CreatorPool>,
 
@@ -76,10 +84,142 @@ pub struct WithdrawFromPool<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetVoteExtension<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator.key().as_ref()],
+        bump = creator_pool.bump,
+        has_one = creator
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeVaultShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator.key().as_ref()],
+        bump = creator_pool.bump,
+        has_one = creator
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
+    pub factory: Account<'info, Factory>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyVaultShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_pool.creator.as_ref()],
+        bump = creator_pool.bump
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
+    pub factory: Account<'info, Factory>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator.key().as_ref()],
+        bump = creator_pool.bump,
+        has_one = creator
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerInactivityRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_pool.creator.as_ref()],
+        bump = creator_pool.bump
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        constraint = creator_collection.creator == creator_pool.creator @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        seeds = [b"sol_vault", creator_pool.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    pub holder: Signer<'info>,
+
+    pub factory: Account<'info, Factory>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPoolRefund<'info> {
+    #[account(
+        seeds = [b"creator_pool", creator_pool.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Refunding @ ErrorCode::PoolNotRefunding
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_pool.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = PoolRefundReceipt::LEN,
+        seeds = [b"pool_refund", creator_pool.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub refund_receipt: Account<'info, PoolRefundReceipt>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 pub fn create_pool(
     ctx: Context<CreateCreatorPool>,
     voting_quorum: u64,
     voting_window: i64,
+    vault_share_bps: u64,
 ) -> Result<()> {
     let creator_pool = &mut ctx.accounts.creator_pool;
     let factory = &ctx.accounts.factory;
@@ -102,6 +242,23 @@ pub fn create_pool(
         factory.default_voting_window
     };
 
+    creator_pool.vault_share_bps = if vault_share_bps > 0 {
+        vault_share_bps
+    } else {
+        factory.default_vault_share_bps
+    };
+    require!(
+        factory.is_valid_vault_share(creator_pool.vault_share_bps),
+        ErrorCode::VaultShareOutOfBounds
+    );
+    creator_pool.pending_vault_share_bps = 0;
+    creator_pool.vault_share_effective_at = 0;
+
+    creator_pool.vote_extension_window = 0;
+    creator_pool.vote_extension_duration = 0;
+    creator_pool.max_vote_extension = 0;
+    creator_pool.last_heartbeat = Clock::get()?.unix_timestamp;
+
     creator_pool.status = PoolStatus::Active;
     creator_pool.bump = ctx.bumps.creator_pool;
 
@@ -127,12 +284,14 @@ pub fn create_pool(
     let creator_key = creator_pool.creator;
     let voting_quorum = creator_pool.voting_quorum;
     let voting_window = creator_pool.voting_window;
+    let vault_share_bps = creator_pool.vault_share_bps;
 
     emit!(CreatorPoolCreated {
         creator: creator_key,
         pool: pool_key,
         voting_quorum,
         voting_window,
+        vault_share_bps,
     });
 
     Ok(())
@@ -158,6 +317,7 @@ pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
         .total_deposited
         .checked_add(amount)
         .ok_or(ErrorCode::MathOverflow)?;
+    creator_pool.last_heartbeat = Clock::get()?.unix_timestamp;
 
     Ok(())
 }
@@ -190,10 +350,233 @@ pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, amount: u64) -> Result
     Ok(())
 }
 
+pub fn set_vote_extension(
+    ctx: Context<SetVoteExtension>,
+    extension_window: i64,
+    extension_duration: i64,
+    max_extension: i64,
+) -> Result<()> {
+    require!(
+        extension_window >= 0 && extension_duration >= 0 && max_extension >= 0,
+        ErrorCode::InvalidVoteExtension
+    );
+
+    // A zero window or duration disables late-vote extensions for the pool
+    let creator_pool = &mut ctx.accounts.creator_pool;
+    creator_pool.vote_extension_window = extension_window;
+    creator_pool.vote_extension_duration = extension_duration;
+    creator_pool.max_vote_extension = max_extension;
+
+    Ok(())
+}
+
+pub fn propose_vault_share(ctx: Context<ProposeVaultShare>, vault_share_bps: u64) -> Result<()> {
+    let creator_pool = &mut ctx.accounts.creator_pool;
+    let factory = &ctx.accounts.factory;
+
+    require!(
+        factory.is_valid_vault_share(vault_share_bps),
+        ErrorCode::VaultShareOutOfBounds
+    );
+
+    // Raising the vault share only strengthens what fans were promised, so it applies at once
+    if vault_share_bps >= creator_pool.vault_share_bps {
+        let previous_vault_share_bps = creator_pool.vault_share_bps;
+        creator_pool.vault_share_bps = vault_share_bps;
+        creator_pool.pending_vault_share_bps = 0;
+        creator_pool.vault_share_effective_at = 0;
+
+        emit!(VaultShareUpdated {
+            creator_pool: creator_pool.key(),
+            previous_vault_share_bps,
+            vault_share_bps,
+        });
+
+        return Ok(());
+    }
+
+    creator_pool.pending_vault_share_bps = vault_share_bps;
+    creator_pool.vault_share_effective_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(factory.vault_share_timelock)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(VaultShareChangeProposed {
+        creator_pool: creator_pool.key(),
+        current_vault_share_bps: creator_pool.vault_share_bps,
+        pending_vault_share_bps: vault_share_bps,
+        effective_at: creator_pool.vault_share_effective_at,
+    });
+
+    Ok(())
+}
+
+pub fn apply_vault_share(ctx: Context<ApplyVaultShare>) -> Result<()> {
+    let creator_pool = &mut ctx.accounts.creator_pool;
+
+    require!(
+        creator_pool.vault_share_effective_at > 0,
+        ErrorCode::NoPendingVaultShare
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= creator_pool.vault_share_effective_at,
+        ErrorCode::VaultShareTimelocked
+    );
+    // The factory bounds may have moved while the change was pending
+    require!(
+        ctx.accounts
+            .factory
+            .is_valid_vault_share(creator_pool.pending_vault_share_bps),
+        ErrorCode::VaultShareOutOfBounds
+    );
+
+    let previous_vault_share_bps = creator_pool.vault_share_bps;
+    creator_pool.vault_share_bps = creator_pool.pending_vault_share_bps;
+    creator_pool.pending_vault_share_bps = 0;
+    creator_pool.vault_share_effective_at = 0;
+
+    emit!(VaultShareUpdated {
+        creator_pool: creator_pool.key(),
+        previous_vault_share_bps,
+        vault_share_bps: creator_pool.vault_share_bps,
+    });
+
+    Ok(())
+}
+
+pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+    let creator_pool = &mut ctx.accounts.creator_pool;
+    creator_pool.last_heartbeat = Clock::get()?.unix_timestamp;
+
+    emit!(CreatorHeartbeat {
+        creator: creator_pool.creator,
+        creator_pool: creator_pool.key(),
+        last_heartbeat: creator_pool.last_heartbeat,
+    });
+
+    Ok(())
+}
+
+pub fn trigger_inactivity_refund(ctx: Context<TriggerInactivityRefund>) -> Result<()> {
+    let creator_pool = &mut ctx.accounts.creator_pool;
+    let inactivity_period = ctx.accounts.factory.inactivity_period;
+
+    require_pass_holder(
+        &ctx.accounts.nft_ownership,
+        &ctx.accounts.holder.key(),
+        &creator_pool.creator,
+    )?;
+
+    // A zero inactivity period disables the switch
+    require!(inactivity_period > 0, ErrorCode::CreatorStillActive);
+    let inactive_since = creator_pool
+        .last_heartbeat
+        .checked_add(inactivity_period)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        Clock::get()?.unix_timestamp >= inactive_since,
+        ErrorCode::CreatorStillActive
+    );
+
+    begin_pool_refund(
+        creator_pool,
+        &ctx.accounts.sol_vault,
+        ctx.accounts.creator_collection.total_supply,
+    )?;
+
+    emit!(InactivityRefundTriggered {
+        creator_pool: creator_pool.key(),
+        triggered_by: ctx.accounts.holder.key(),
+        last_heartbeat: creator_pool.last_heartbeat,
+    });
+
+    Ok(())
+}
+
+// Freezes the vault balance and pass supply so every pass redeems the same share
+pub fn begin_pool_refund<'info>(
+    creator_pool: &mut Account<'info, CreatorPool>,
+    sol_vault: &AccountInfo<'info>,
+    pass_supply: u64,
+) -> Result<()> {
+    require!(
+        creator_pool.status == PoolStatus::Active,
+        ErrorCode::PoolNotActive
+    );
+    require!(pass_supply > 0, ErrorCode::NoPassHolders);
+
+    let refundable_lamports = unreserved_vault_lamports(sol_vault)?;
+
+    creator_pool.status = PoolStatus::Refunding;
+    creator_pool.refund_snapshot_lamports = refundable_lamports;
+    creator_pool.refund_snapshot_supply = pass_supply;
+
+    emit!(PoolRefundStarted {
+        creator_pool: creator_pool.key(),
+        refundable_lamports,
+        pass_supply,
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_pool_refund(ctx: Context<WithdrawPoolRefund>) -> Result<()> {
+    let creator_pool = &ctx.accounts.creator_pool;
+    let nft_ownership = &ctx.accounts.nft_ownership;
+
+    // Expired passes are still part of the refund snapshot supply
+    require_pass_owner(
+        nft_ownership,
+        &ctx.accounts.holder.key(),
+        &creator_pool.creator,
+    )?;
+
+    let amount = creator_pool.refund_snapshot_lamports / creator_pool.refund_snapshot_supply;
+    require!(amount > 0, ErrorCode::InsufficientFunds);
+
+    ctx.accounts.sol_vault.sub_lamports(amount)?;
+    ctx.accounts.holder.add_lamports(amount)?;
+
+    let refund_receipt = &mut ctx.accounts.refund_receipt;
+    refund_receipt.creator_pool = creator_pool.key();
+    refund_receipt.holder = ctx.accounts.holder.key();
+    refund_receipt.nft_mint = nft_ownership.nft_mint;
+    refund_receipt.amount = amount;
+    refund_receipt.withdrawn_at = Clock::get()?.unix_timestamp;
+    refund_receipt.bump = ctx.bumps.refund_receipt;
+
+    emit!(PoolRefundWithdrawn {
+        creator_pool: creator_pool.key(),
+        holder: refund_receipt.holder,
+        nft_mint: refund_receipt.nft_mint,
+        amount,
+    });
+
+    Ok(())
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Math overflow")]
     MathOverflow,
     #[msg("Insufficient funds")]
     InsufficientFunds,
+    #[msg("Vote extension parameters must not be negative")]
+    InvalidVoteExtension,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Creator pool is not refunding")]
+    PoolNotRefunding,
+    #[msg("Creator pool has no pass holders to refund")]
+    NoPassHolders,
+    #[msg("Creator collection does not belong to this pool's creator")]
+    InvalidCreatorCollection,
+    #[msg("Creator has not been inactive long enough")]
+    CreatorStillActive,
+    #[msg("Vault share is outside the factory bounds")]
+    VaultShareOutOfBounds,
+    #[msg("No vault share change is pending")]
+    NoPendingVaultShare,
+    #[msg("Vault share change is still timelocked")]
+    VaultShareTimelocked,
 }
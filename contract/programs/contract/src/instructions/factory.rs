@@ -1,5 +1,8 @@
-use crate::events::FactoryInitialized;
-use crate::state::Factory;
+use crate::events::{
+    CreatorFeeOverrideRemoved, CreatorFeeOverrideSet, FactoryInitialized, FactoryUpdated,
+};
+use crate::fees::{BPS_DENOMINATOR, DEFAULT_VAULT_SHARE_BPS};
+use crate::state::{CreatorFeeOverride, Factory};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
@@ -22,26 +25,253 @@ pub struct InitializeFactory<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateFactory<'info> {
+    #[account(
+        mut,
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority
+    )]
+    pub factory: Account<'info, Factory>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct SetCreatorFeeOverride<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CreatorFeeOverride::LEN,
+        seeds = [b"fee_override", creator.as_ref()],
+        bump
+    )]
+    pub fee_override: Account<'info, CreatorFeeOverride>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority
+    )]
+    pub factory: Account<'info, Factory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveCreatorFeeOverride<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_override", fee_override.creator.as_ref()],
+        bump = fee_override.bump,
+        close = authority
+    )]
+    pub fee_override: Account<'info, CreatorFeeOverride>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority
+    )]
+    pub factory: Account<'info, Factory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+// Fields left as None keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateFactoryParams {
+    pub platform_fee_bps: Option<u64>,
+    pub crank_bounty: Option<u64>,
+    pub filing_bond_amount: Option<u64>,
+    pub filing_bond_bps: Option<u64>,
+    pub misconduct_cosign_threshold: Option<u64>,
+    pub inactivity_period: Option<i64>,
+    pub default_vault_share_bps: Option<u64>,
+    pub min_vault_share_bps: Option<u64>,
+    pub max_vault_share_bps: Option<u64>,
+    pub vault_share_timelock: Option<i64>,
+    pub revocation_cooldown: Option<i64>,
+}
+
 pub fn initialize_factory(
     ctx: Context<InitializeFactory>,
     default_quorum: u64,
     default_voting_window: i64,
-    platform_fee_percentage: u64,
+    platform_fee_bps: u64,
+    max_platform_fee_bps: u64,
 ) -> Result<()> {
+    require!(
+        max_platform_fee_bps <= BPS_DENOMINATOR,
+        ErrorCode::InvalidBasisPoints
+    );
+    require!(
+        platform_fee_bps <= max_platform_fee_bps,
+        ErrorCode::PlatformFeeTooHigh
+    );
+
     let factory = &mut ctx.accounts.factory;
     factory.authority = ctx.accounts.authority.key();
     factory.default_quorum = default_quorum;
     factory.default_voting_window = default_voting_window;
-    factory.platform_fee_percentage = platform_fee_percentage;
+    factory.platform_fee_bps = platform_fee_bps;
+    factory.max_platform_fee_bps = max_platform_fee_bps;
     factory.usdc_mint = ctx.accounts.usdc_mint.key();
     factory.bump = ctx.bumps.factory;
+    factory.crank_bounty = 0;
+    factory.filing_bond_amount = 0;
+    factory.filing_bond_bps = 0;
+    factory.misconduct_cosign_threshold = 0;
+    factory.inactivity_period = 0;
+    factory.min_vault_share_bps = 0;
+    factory.max_vault_share_bps = BPS_DENOMINATOR - max_platform_fee_bps;
+    factory.default_vault_share_bps = DEFAULT_VAULT_SHARE_BPS.min(factory.max_vault_share_bps);
+    factory.vault_share_timelock = Factory::MIN_VAULT_SHARE_TIMELOCK;
+    factory.revocation_cooldown = 0;
     emit!(FactoryInitialized {
         authority: factory.authority,
         default_quorum: factory.default_quorum,
         default_voting_window: factory.default_voting_window,
-        platform_fee_percentage: factory.platform_fee_percentage,
+        platform_fee_bps: factory.platform_fee_bps,
+        max_platform_fee_bps: factory.max_platform_fee_bps,
         usdc_mint: factory.usdc_mint,
     });
 
     Ok(())
 }
+
+pub fn update_factory(ctx: Context<UpdateFactory>, params: UpdateFactoryParams) -> Result<()> {
+    let factory = &mut ctx.accounts.factory;
+
+    if let Some(platform_fee_bps) = params.platform_fee_bps {
+        require!(
+            platform_fee_bps <= factory.max_platform_fee_bps,
+            ErrorCode::PlatformFeeTooHigh
+        );
+        factory.platform_fee_bps = platform_fee_bps;
+    }
+    if let Some(crank_bounty) = params.crank_bounty {
+        factory.crank_bounty = crank_bounty;
+    }
+    if let Some(filing_bond_amount) = params.filing_bond_amount {
+        factory.filing_bond_amount = filing_bond_amount;
+    }
+    if let Some(filing_bond_bps) = params.filing_bond_bps {
+        require!(
+            filing_bond_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidBasisPoints
+        );
+        factory.filing_bond_bps = filing_bond_bps;
+    }
+    if let Some(misconduct_cosign_threshold) = params.misconduct_cosign_threshold {
+        factory.misconduct_cosign_threshold = misconduct_cosign_threshold;
+    }
+    if let Some(inactivity_period) = params.inactivity_period {
+        require!(inactivity_period >= 0, ErrorCode::InvalidInactivityPeriod);
+        factory.inactivity_period = inactivity_period;
+    }
+    if let Some(default_vault_share_bps) = params.default_vault_share_bps {
+        factory.default_vault_share_bps = default_vault_share_bps;
+    }
+    if let Some(min_vault_share_bps) = params.min_vault_share_bps {
+        factory.min_vault_share_bps = min_vault_share_bps;
+    }
+    if let Some(max_vault_share_bps) = params.max_vault_share_bps {
+        factory.max_vault_share_bps = max_vault_share_bps;
+    }
+    if let Some(vault_share_timelock) = params.vault_share_timelock {
+        require!(
+            vault_share_timelock >= Factory::MIN_VAULT_SHARE_TIMELOCK,
+            ErrorCode::VaultShareTimelockTooShort
+        );
+        factory.vault_share_timelock = vault_share_timelock;
+    }
+    if let Some(revocation_cooldown) = params.revocation_cooldown {
+        require!(revocation_cooldown >= 0, ErrorCode::InvalidTimelock);
+        factory.revocation_cooldown = revocation_cooldown;
+    }
+    require!(
+        factory
+            .max_vault_share_bps
+            .saturating_add(factory.max_platform_fee_bps)
+            <= BPS_DENOMINATOR
+            && factory.is_valid_vault_share(factory.default_vault_share_bps),
+        ErrorCode::InvalidVaultShareBounds
+    );
+
+    emit!(FactoryUpdated {
+        authority: factory.authority,
+        platform_fee_bps: factory.platform_fee_bps,
+        crank_bounty: factory.crank_bounty,
+        filing_bond_amount: factory.filing_bond_amount,
+        filing_bond_bps: factory.filing_bond_bps,
+        misconduct_cosign_threshold: factory.misconduct_cosign_threshold,
+        inactivity_period: factory.inactivity_period,
+        default_vault_share_bps: factory.default_vault_share_bps,
+        min_vault_share_bps: factory.min_vault_share_bps,
+        max_vault_share_bps: factory.max_vault_share_bps,
+        vault_share_timelock: factory.vault_share_timelock,
+        revocation_cooldown: factory.revocation_cooldown,
+    });
+
+    Ok(())
+}
+
+pub fn set_creator_fee_override(
+    ctx: Context<SetCreatorFeeOverride>,
+    creator: Pubkey,
+    platform_fee_bps: u64,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        platform_fee_bps <= ctx.accounts.factory.max_platform_fee_bps,
+        ErrorCode::PlatformFeeTooHigh
+    );
+    require!(expires_at >= 0, ErrorCode::InvalidExpiry);
+
+    let fee_override = &mut ctx.accounts.fee_override;
+    fee_override.creator = creator;
+    fee_override.platform_fee_bps = platform_fee_bps;
+    fee_override.expires_at = expires_at;
+    fee_override.bump = ctx.bumps.fee_override;
+
+    emit!(CreatorFeeOverrideSet {
+        creator,
+        platform_fee_bps,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+pub fn remove_creator_fee_override(ctx: Context<RemoveCreatorFeeOverride>) -> Result<()> {
+    emit!(CreatorFeeOverrideRemoved {
+        creator: ctx.accounts.fee_override.creator,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBasisPoints,
+    #[msg("Platform fee exceeds the factory maximum")]
+    PlatformFeeTooHigh,
+    #[msg("Inactivity period must not be negative")]
+    InvalidInactivityPeriod,
+    #[msg("Vault share bounds must satisfy min <= default <= max <= 10000 - max platform fee")]
+    InvalidVaultShareBounds,
+    #[msg("Timelock must not be negative")]
+    InvalidTimelock,
+    #[msg("Expiry must not be negative")]
+    InvalidExpiry,
+    #[msg("Vault share timelock is below the minimum")]
+    VaultShareTimelockTooShort,
+}
@@ -1,6 +1,25 @@
-use crate::events::NftSaleRevenueDistributed;
-use crate::state::{CreatorPool, Factory};
+use crate::events::{NftSaleRevenueDistributed, PassPurchased, PassRenewed, ReferralRewarded};
+use crate::fees::{bps_of, split_sale, SaleSplit};
+use crate::instructions::pass_ownership::{fan_pass_info, TokenPass};
+use crate::instructions::transfer_hook::{extra_account_metas_len, init_extra_account_metas};
+use crate::merkle;
+use crate::state::{
+    CreatorCollection, CreatorFeeOverride, CreatorPool, Factory, NftOwnership, PassPurchaseCounter,
+    PoolStatus, ReferrerStats, Treasury,
+};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_interface::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_interface::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, InitializeMint2, Mint, MintTo,
+    NonTransferableMintInitialize, SetAuthority, TokenAccount, TransferHookInitialize,
+};
 
 #[derive(Accounts)]
 pub struct DepositFromNftSale<'info> {
@@ -26,24 +45,225 @@ pub struct DepositFromNftSale<'info> {
     #[account(mut)]
     pub creator_wallet: AccountInfo<'info>,
 
-    /// CHECK: This is a regular account that can receive native SOL
-    #[account(mut)]
-    pub platform_wallet: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
     pub factory: Account<'info, Factory>,
 
+    /// CHECK: The creator's fee override PDA, uninitialized when none was negotiated
+    #[account(
+        seeds = [b"fee_override", creator.key().as_ref()],
+        bump
+    )]
+    pub fee_override: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct BuyPass<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator_collection.creator.as_ref()],
+        bump = creator_collection.bump,
+        constraint = !creator_collection.is_sold_out() @ ErrorCode::SoldOut
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_collection.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Receives the creator's share of the sale
+    #[account(
+        mut,
+        address = creator_collection.creator @ ErrorCode::InvalidCreatorWallet
+    )]
+    pub creator_wallet: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
+    pub factory: Box<Account<'info, Factory>>,
+
+    /// CHECK: The creator's fee override PDA, uninitialized when none was negotiated
+    #[account(
+        seeds = [b"fee_override", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub fee_override: AccountInfo<'info>,
+
+    /// CHECK: Created in the handler, since soulbound collections add the NonTransferable extension
+    #[account(mut)]
+    pub pass_mint: Signer<'info>,
+
+    /// CHECK: Extra-account-metas PDA read by Token-2022 to resolve the transfer hook accounts
+    #[account(
+        init,
+        payer = buyer,
+        space = extra_account_metas_len(),
+        seeds = [b"extra-account-metas", pass_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    /// CHECK: The buyer's pass ATA, created by the associated token program once the mint exists
+    #[account(mut)]
+    pub buyer_token_account: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = NftOwnership::LEN,
+        seeds = [b"nft_ownership", pass_mint.key().as_ref()],
+        bump
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PassPurchaseCounter::LEN,
+        seeds = [b"purchase_counter", creator_collection.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_counter: Box<Account<'info, PassPurchaseCounter>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_stats", referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
+
+    /// CHECK: Receives the referral reward, must match `referrer_stats.referrer`
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewPass<'info> {
+    #[account(
+        address = nft_ownership.creator_collection @ ErrorCode::InvalidNftCollection,
+        constraint = creator_collection.pass_duration > 0 @ ErrorCode::PassNotRenewable
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_collection.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Receives the creator's share of the renewal
+    #[account(
+        mut,
+        address = creator_collection.creator @ ErrorCode::InvalidCreatorWallet
+    )]
+    pub creator_wallet: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
+    pub factory: Box<Account<'info, Factory>>,
+
+    /// CHECK: The creator's fee override PDA, uninitialized when none was negotiated
+    #[account(
+        seeds = [b"fee_override", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub fee_override: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump,
+        constraint = !nft_ownership.revoked @ ErrorCode::PassRevoked
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    // Anyone may pay for a renewal, so fans can gift a membership month
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FanPassInfo {
+    pub holder: Pubkey,
+    pub creator: Pubkey,
+    pub tier: u8,
+    pub acquired_at: i64,
+    pub valid_until: i64,
+}
+
 #[derive(Accounts)]
 pub struct VerifyFanPass<'info> {
     pub fan: Signer<'info>,
 
-    pub nft_mint: Account<'info, anchor_spl::token::Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    pub fan_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"creator_collection", nft_mint.key().as_ref()],
-        bump
+        seeds = [b"creator_collection", nft_ownership.creator.as_ref()],
+        bump = creator_collection.bump
     )]
     pub creator_collection: Account<'info, crate::state::CreatorCollection>,
 
@@ -52,85 +272,563 @@ pub struct VerifyFanPass<'info> {
         bump
     )]
     pub nft_ownership: Account<'info, crate::state::NftOwnership>,
+
+    #[account(
+        seeds = [b"metadata", Metadata::id().as_ref(), nft_mint.key().as_ref()],
+        seeds::program = Metadata::id(),
+        bump
+    )]
+    pub nft_metadata: Option<Account<'info, MetadataAccount>>,
 }
 
-pub fn deposit_from_nft_sale(ctx: Context<DepositFromNftSale>, total_amount: u64) -> Result<()> {
-    let creator_pool = &mut ctx.accounts.creator_pool;
-    let factory = &ctx.accounts.factory;
+// Computes the platform, creator and vault shares of a sale for this creator
+fn quote_sale(
+    factory: &Factory,
+    creator_pool: &CreatorPool,
+    fee_override: &AccountInfo,
+    total_amount: u64,
+) -> Result<SaleSplit> {
+    let fee_override = CreatorFeeOverride::load(fee_override)?;
 
-    let platform_fee = (total_amount * factory.platform_fee_percentage) / 100;
-    let vault_amount = (total_amount * 70) / 100; // 70% to vault
-    let creator_amount = total_amount - platform_fee - vault_amount;
+    let split = split_sale(
+        total_amount,
+        factory.platform_fee_bps_for(fee_override.as_ref(), Clock::get()?.unix_timestamp),
+        creator_pool.vault_share_bps,
+    )
+    .ok_or(ErrorCode::InvalidFeeSplit)?;
 
-    // Transfer to SOL vault using PDA authority
-    let vault_bump = ctx.bumps.sol_vault;
-    let vault_seeds = &[b"sol_vault", creator_pool.creator.as_ref(), &[vault_bump]];
-    let signer = &[&vault_seeds[..]];
+    Ok(split)
+}
 
-    // Transfer to vault (locked until claim resolution)
-    anchor_lang::system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.sol_vault.to_account_info(),
+// Creates a pass mint whose transfer hook authority is the collection PDA. Soulbound
+// collections also get the NonTransferable extension, which still allows burning.
+fn create_pass_mint<'info>(
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    pass_mint: &AccountInfo<'info>,
+    creator_collection: &Pubkey,
+    soulbound: bool,
+) -> Result<()> {
+    let mut extensions = vec![ExtensionType::TransferHook];
+    if soulbound {
+        extensions.push(ExtensionType::NonTransferable);
+    }
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+    anchor_lang::system_program::create_account(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::CreateAccount {
+                from: payer.clone(),
+                to: pass_mint.clone(),
             },
-            signer,
         ),
-        vault_amount,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        token_program.key,
     )?;
 
-    // Transfer to creator (immediate)
-    anchor_lang::system_program::transfer(
+    token_interface::transfer_hook_initialize(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.creator_wallet.to_account_info(),
+            token_program.clone(),
+            TransferHookInitialize {
+                token_program_id: token_program.clone(),
+                mint: pass_mint.clone(),
             },
         ),
-        creator_amount,
+        Some(*creator_collection),
+        Some(crate::ID),
     )?;
 
-    // Transfer to platform
-    anchor_lang::system_program::transfer(
+    if soulbound {
+        token_interface::non_transferable_mint_initialize(CpiContext::new(
+            token_program.clone(),
+            NonTransferableMintInitialize {
+                token_program_id: token_program.clone(),
+                mint: pass_mint.clone(),
+            },
+        ))?;
+    }
+
+    token_interface::initialize_mint2(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.platform_wallet.to_account_info(),
+            token_program.clone(),
+            InitializeMint2 {
+                mint: pass_mint.clone(),
             },
         ),
-        platform_fee,
-    )?;
+        0,
+        creator_collection,
+        None,
+    )
+}
+
+// Moves each share of a sale out of the payer's wallet and records it on the pool and treasury
+fn pay_sale<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    sol_vault: &AccountInfo<'info>,
+    creator_wallet: &AccountInfo<'info>,
+    treasury: &mut Account<'info, Treasury>,
+    creator_pool: &mut Account<'info, CreatorPool>,
+    split: &SaleSplit,
+) -> Result<()> {
+    let transfers = [
+        // Transfer to vault (locked until claim resolution)
+        (sol_vault.clone(), split.vault_amount),
+        // Transfer to creator (immediate)
+        (creator_wallet.clone(), split.creator_amount),
+        // Transfer to platform treasury
+        (treasury.to_account_info(), split.platform_fee),
+    ];
+
+    for (to, amount) in transfers {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to,
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(split.platform_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     creator_pool.total_deposited = creator_pool
         .total_deposited
-        .checked_add(vault_amount)
+        .checked_add(split.vault_amount)
         .ok_or(ErrorCode::MathOverflow)?;
 
+    Ok(())
+}
+
+pub fn deposit_from_nft_sale(ctx: Context<DepositFromNftSale>, total_amount: u64) -> Result<()> {
+    let split = quote_sale(
+        &ctx.accounts.factory,
+        &ctx.accounts.creator_pool,
+        &ctx.accounts.fee_override,
+        total_amount,
+    )?;
+
+    pay_sale(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator_wallet,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.creator_pool,
+        &split,
+    )?;
+
+    // Only creator-signed actions count as signs of life; fan purchases don't
+    ctx.accounts.creator_pool.last_heartbeat = Clock::get()?.unix_timestamp;
+
     emit!(NftSaleRevenueDistributed {
-        creator: creator_pool.creator,
+        creator: ctx.accounts.creator_pool.creator,
         creator_pool: ctx.accounts.creator_pool.key(),
         total_amount,
-        creator_pool_amount: vault_amount,
-        creator_amount,
-        platform_fee,
+        creator_pool_amount: split.vault_amount,
+        creator_amount: split.creator_amount,
+        platform_fee: split.platform_fee,
     });
 
     Ok(())
 }
 
-pub fn verify_fan_pass(ctx: Context<VerifyFanPass>) -> Result<()> {
+pub fn buy_pass(ctx: Context<BuyPass>, max_price: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let creator_collection = &ctx.accounts.creator_collection;
+    let buyer = ctx.accounts.buyer.key();
+
+    let presale = creator_collection.in_presale(now);
+    let price = if presale {
+        require!(
+            now >= creator_collection.presale_starts_at,
+            ErrorCode::PresaleNotStarted
+        );
+        require!(
+            merkle::verify(
+                &proof,
+                &creator_collection.presale_merkle_root,
+                merkle::leaf(&buyer)
+            ),
+            ErrorCode::NotOnAllowlist
+        );
+        creator_collection.presale_price
+    } else {
+        creator_collection
+            .next_price()
+            .ok_or(ErrorCode::MathOverflow)?
+    };
+    require!(price <= max_price, ErrorCode::PriceAboveMax);
+
+    let max_per_wallet = ctx.accounts.creator_collection.max_per_wallet;
+    let purchase_counter = &mut ctx.accounts.purchase_counter;
+    if purchase_counter.purchased == 0 {
+        purchase_counter.creator_collection = ctx.accounts.creator_collection.key();
+        purchase_counter.buyer = buyer;
+        purchase_counter.bump = ctx.bumps.purchase_counter;
+    }
+    if presale {
+        require!(
+            !purchase_counter.presale_claimed,
+            ErrorCode::PresaleAlreadyClaimed
+        );
+        purchase_counter.presale_claimed = true;
+    }
     require!(
-        ctx.accounts.nft_ownership.owner == ctx.accounts.fan.key(),
-        ErrorCode::NotNftOwner
+        max_per_wallet == 0 || purchase_counter.purchased < max_per_wallet,
+        ErrorCode::WalletLimitReached
     );
+    purchase_counter.purchased = purchase_counter
+        .purchased
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
 
+    let mut split = quote_sale(
+        &ctx.accounts.factory,
+        &ctx.accounts.creator_pool,
+        &ctx.accounts.fee_override,
+        price,
+    )?;
+
+    // Referral rewards come out of the creator's share, never the vault or the platform fee
     require!(
-        ctx.accounts.nft_ownership.creator_collection == ctx.accounts.creator_collection.key(),
+        ctx.accounts.referrer_stats.is_some() == ctx.accounts.referrer.is_some(),
+        ErrorCode::InvalidReferrer
+    );
+    if let (Some(referrer_stats), Some(referrer)) =
+        (&mut ctx.accounts.referrer_stats, &ctx.accounts.referrer)
+    {
+        require_keys_eq!(
+            referrer.key(),
+            referrer_stats.referrer,
+            ErrorCode::InvalidReferrer
+        );
+        require_keys_neq!(referrer.key(), buyer, ErrorCode::SelfReferral);
+
+        let reward = bps_of(
+            split.creator_amount,
+            ctx.accounts.creator_collection.referral_bps,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+        split.creator_amount = split
+            .creator_amount
+            .checked_sub(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: referrer.clone(),
+                },
+            ),
+            reward,
+        )?;
+
+        referrer_stats.referral_count = referrer_stats
+            .referral_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        referrer_stats.total_earned = referrer_stats
+            .total_earned
+            .checked_add(reward)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(ReferralRewarded {
+            referrer: referrer.key(),
+            buyer,
+            creator_collection: ctx.accounts.creator_collection.key(),
+            amount: reward,
+            referral_count: referrer_stats.referral_count,
+            total_earned: referrer_stats.total_earned,
+        });
+    }
+
+    // The fan pays the listed price, split in the same transaction as the mint
+    pay_sale(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator_wallet,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.creator_pool,
+        &split,
+    )?;
+
+    create_pass_mint(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.creator_collection.key(),
+        ctx.accounts.creator_collection.soulbound,
+    )?;
+
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.buyer.to_account_info(),
+            associated_token: ctx.accounts.buyer_token_account.clone(),
+            authority: ctx.accounts.buyer.to_account_info(),
+            mint: ctx.accounts.pass_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
+    let creator = ctx.accounts.creator_collection.creator;
+    let collection_seeds = &[
+        b"creator_collection",
+        creator.as_ref(),
+        &[ctx.accounts.creator_collection.bump],
+    ];
+    let signer = &[&collection_seeds[..]];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.pass_mint.to_account_info(),
+                to: ctx.accounts.buyer_token_account.clone(),
+                authority: ctx.accounts.creator_collection.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    // Drop the mint authority so each pass stays a single token
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.creator_collection.to_account_info(),
+                account_or_mint: ctx.accounts.pass_mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let nft_ownership = &mut ctx.accounts.nft_ownership;
+    nft_ownership.owner = buyer;
+    nft_ownership.nft_mint = ctx.accounts.pass_mint.key();
+    nft_ownership.creator_collection = ctx.accounts.creator_collection.key();
+    nft_ownership.creator = creator;
+    nft_ownership.registered_at = now;
+    nft_ownership.bump = ctx.bumps.nft_ownership;
+    nft_ownership.acquired_at = now;
+    nft_ownership.tier = if presale {
+        NftOwnership::TIER_PRESALE
+    } else {
+        NftOwnership::TIER_PUBLIC
+    };
+    nft_ownership.valid_until = ctx
+        .accounts
+        .creator_collection
+        .valid_until(now)
+        .ok_or(ErrorCode::MathOverflow)?;
+    nft_ownership.vault_contribution = split.vault_amount;
+
+    init_extra_account_metas(
+        &ctx.accounts.extra_account_meta_list,
+        &ctx.accounts.creator_collection.key(),
+    )?;
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.total_supply = creator_collection
+        .total_supply
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PassPurchased {
+        buyer: nft_ownership.owner,
+        creator,
+        creator_collection: creator_collection.key(),
+        nft_mint: nft_ownership.nft_mint,
+        price,
+        creator_pool_amount: split.vault_amount,
+        creator_amount: split.creator_amount,
+        platform_fee: split.platform_fee,
+        total_supply: creator_collection.total_supply,
+        presale,
+        valid_until: nft_ownership.valid_until,
+    });
+
+    Ok(())
+}
+
+// Extends from the current expiry, or from now once a pass has lapsed
+pub fn renew_pass(ctx: Context<RenewPass>, max_price: u64) -> Result<()> {
+    let price = ctx.accounts.creator_collection.renewal_price;
+    require!(price <= max_price, ErrorCode::PriceAboveMax);
+
+    let split = quote_sale(
+        &ctx.accounts.factory,
+        &ctx.accounts.creator_pool,
+        &ctx.accounts.fee_override,
+        price,
+    )?;
+
+    pay_sale(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator_wallet,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.creator_pool,
+        &split,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let nft_ownership = &mut ctx.accounts.nft_ownership;
+    nft_ownership.valid_until = ctx
+        .accounts
+        .creator_collection
+        .valid_until(std::cmp::max(now, nft_ownership.valid_until))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PassRenewed {
+        payer: ctx.accounts.payer.key(),
+        nft_mint: nft_ownership.nft_mint,
+        creator_collection: ctx.accounts.creator_collection.key(),
+        price,
+        creator_pool_amount: split.vault_amount,
+        creator_amount: split.creator_amount,
+        platform_fee: split.platform_fee,
+        valid_until: nft_ownership.valid_until,
+    });
+
+    Ok(())
+}
+
+// Returns the verified pass details as return data so other programs can gate on it by CPI
+pub fn verify_fan_pass(ctx: Context<VerifyFanPass>) -> Result<FanPassInfo> {
+    require_collection_member(
+        &ctx.accounts.nft_mint,
+        ctx.accounts.nft_metadata.as_deref(),
+        &ctx.accounts.nft_ownership,
+        &ctx.accounts.creator_collection,
+    )?;
+
+    let pass = TokenPass {
+        nft_ownership: &ctx.accounts.nft_ownership,
+        token_account: &ctx.accounts.fan_token_account,
+    };
+
+    fan_pass_info(
+        &pass,
+        &ctx.accounts.fan.key(),
+        &ctx.accounts.creator_collection,
+    )
+}
+
+// Checks that the holder's token account actually holds the pass, not just the ownership PDA
+pub fn require_pass_token(
+    token_account: &InterfaceAccount<TokenAccount>,
+    nft_mint: &Pubkey,
+    holder: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        token_account.mint,
+        *nft_mint,
+        ErrorCode::InvalidPassTokenAccount
+    );
+    require_keys_eq!(token_account.owner, *holder, ErrorCode::NotNftOwner);
+    require!(token_account.amount == 1, ErrorCode::NotNftOwner);
+
+    Ok(())
+}
+
+// Metaplex passes must carry a verified collection matching the creator's collection mint.
+// Passes minted by buy_pass have no Metaplex metadata. Anyone can point a mint's hook
+// authority at the collection PDA, so they must also have the nft_ownership record that
+// only buy_pass creates, seeded by this mint under this program.
+pub fn require_collection_member(
+    nft_mint: &InterfaceAccount<Mint>,
+    nft_metadata: Option<&MetadataAccount>,
+    nft_ownership: &Account<NftOwnership>,
+    creator_collection: &Account<CreatorCollection>,
+) -> Result<()> {
+    if let Some(metadata) = nft_metadata {
+        let verified = metadata.collection.as_ref().is_some_and(|collection| {
+            collection.verified && collection.key == creator_collection.collection_mint
+        });
+        require!(verified, ErrorCode::InvalidNftCollection);
+        return Ok(());
+    }
+
+    let (expected_ownership, _) =
+        Pubkey::find_program_address(&[b"nft_ownership", nft_mint.key().as_ref()], &crate::ID);
+    require_keys_eq!(
+        nft_ownership.key(),
+        expected_ownership,
+        ErrorCode::InvalidNftCollection
+    );
+    require_keys_eq!(
+        nft_ownership.nft_mint,
+        nft_mint.key(),
+        ErrorCode::InvalidNftCollection
+    );
+    require_keys_eq!(
+        nft_ownership.creator_collection,
+        creator_collection.key(),
+        ErrorCode::InvalidNftCollection
+    );
+
+    let mint_data = nft_mint.to_account_info().try_borrow_data()?.to_vec();
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    let hook_authority = mint_state
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|hook| Option::<Pubkey>::from(hook.authority));
+    require!(
+        hook_authority == Some(creator_collection.key()),
+        ErrorCode::InvalidNftCollection
+    );
+
+    Ok(())
+}
+
+// Checks that a registered pass belongs to the holder, was issued by the creator and
+// hasn't been revoked, whether or not it has expired
+pub fn require_pass_owner(
+    nft_ownership: &NftOwnership,
+    holder: &Pubkey,
+    creator: &Pubkey,
+) -> Result<()> {
+    require!(nft_ownership.owner == *holder, ErrorCode::NotNftOwner);
+    require!(
+        nft_ownership.creator == *creator,
         ErrorCode::InvalidNftCollection
     );
+    require!(!nft_ownership.revoked, ErrorCode::PassRevoked);
+
+    Ok(())
+}
+
+// Like require_pass_owner, but the pass must also still be within its validity period
+pub fn require_pass_holder(
+    nft_ownership: &NftOwnership,
+    holder: &Pubkey,
+    creator: &Pubkey,
+) -> Result<()> {
+    require_pass_owner(nft_ownership, holder, creator)?;
+    require!(
+        !nft_ownership.is_expired(Clock::get()?.unix_timestamp),
+        ErrorCode::PassExpired
+    );
 
     Ok(())
 }
@@ -143,4 +841,36 @@ pub enum ErrorCode {
     NotNftOwner,
     #[msg("Invalid NFT collection")]
     InvalidNftCollection,
+    #[msg("Fee and vault shares exceed the sale amount")]
+    InvalidFeeSplit,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Creator wallet does not match the collection creator")]
+    InvalidCreatorWallet,
+    #[msg("Pass price is above the buyer's maximum")]
+    PriceAboveMax,
+    #[msg("All passes in this collection have been sold")]
+    SoldOut,
+    #[msg("Only collections with a max supply can register compressed or Core passes")]
+    UncappedCollection,
+    #[msg("This wallet has reached the collection's purchase limit")]
+    WalletLimitReached,
+    #[msg("The presale has not started yet")]
+    PresaleNotStarted,
+    #[msg("Wallet is not on the presale allowlist")]
+    NotOnAllowlist,
+    #[msg("Wallet has already claimed its presale pass")]
+    PresaleAlreadyClaimed,
+    #[msg("Referrer does not match the referrer stats account")]
+    InvalidReferrer,
+    #[msg("Buyers cannot refer themselves")]
+    SelfReferral,
+    #[msg("Token account does not hold this pass")]
+    InvalidPassTokenAccount,
+    #[msg("Pass has expired")]
+    PassExpired,
+    #[msg("Passes in this collection do not expire")]
+    PassNotRenewable,
+    #[msg("Pass has been revoked by the creator")]
+    PassRevoked,
 }
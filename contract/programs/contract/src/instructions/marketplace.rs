@@ -0,0 +1,487 @@
+use crate::events::{PassListed, PassListingCancelled, PassSold, PassTransferApproved};
+use crate::fees::{bps_of, split_sale, SaleSplit};
+use crate::instructions::transfer_hook::transfer_pass;
+use crate::state::{
+    CreatorCollection, CreatorPool, NftOwnership, PassListing, PoolStatus, TransferApproval,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ListPass<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = PassListing::LEN,
+        seeds = [b"listing", pass_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Box<Account<'info, PassListing>>,
+
+    #[account(
+        seeds = [b"nft_ownership", pass_mint.key().as_ref()],
+        bump = nft_ownership.bump,
+        constraint = nft_ownership.owner == seller.key() @ ErrorCode::NotNftOwner,
+        constraint = !nft_ownership.revoked @ ErrorCode::PassRevoked
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    #[account(
+        address = nft_ownership.creator_collection @ ErrorCode::InvalidCreatorCollection,
+        constraint = !creator_collection.soulbound @ ErrorCode::SoulboundPass
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    pub pass_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pass_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = pass_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(
+        mut,
+        seeds = [b"listing", pass_mint.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller
+    )]
+    pub listing: Box<Account<'info, PassListing>>,
+
+    #[account(
+        address = listing.creator_collection @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    pub pass_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pass_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pass_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BuyListedPass<'info> {
+    #[account(
+        mut,
+        seeds = [b"listing", pass_mint.key().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller
+    )]
+    pub listing: Box<Account<'info, PassListing>>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_ownership", pass_mint.key().as_ref()],
+        bump = nft_ownership.bump,
+        constraint = !nft_ownership.revoked @ ErrorCode::PassRevoked
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    #[account(
+        address = listing.creator_collection @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_collection.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Receives the creator's share of the royalty
+    #[account(
+        mut,
+        address = creator_collection.creator @ ErrorCode::InvalidCreatorWallet
+    )]
+    pub creator_wallet: AccountInfo<'info>,
+
+    pub pass_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pass_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = pass_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Receives the sale proceeds and the listing rent, must match `listing.seller`
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApprovePassTransfer<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = TransferApproval::LEN,
+        seeds = [b"transfer_approval", nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub transfer_approval: Box<Account<'info, TransferApproval>>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump,
+        constraint = nft_ownership.owner == owner.key() @ ErrorCode::NotNftOwner,
+        constraint = !nft_ownership.revoked @ ErrorCode::PassRevoked
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    #[account(
+        address = nft_ownership.creator_collection @ ErrorCode::InvalidCreatorCollection,
+        constraint = !creator_collection.soulbound @ ErrorCode::SoulboundPass
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_collection.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    /// CHECK: Receives the creator's share of the royalty
+    #[account(
+        mut,
+        address = creator_collection.creator @ ErrorCode::InvalidCreatorWallet
+    )]
+    pub creator_wallet: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Not NFT owner")]
+    NotNftOwner,
+    #[msg("Listing does not belong to this creator collection")]
+    InvalidCreatorCollection,
+    #[msg("Creator wallet does not match the collection creator")]
+    InvalidCreatorWallet,
+    #[msg("Listing price is above the buyer's maximum")]
+    PriceAboveMax,
+    #[msg("Buyers cannot purchase their own listing")]
+    SelfPurchase,
+    #[msg("Royalty and vault shares exceed the sale amount")]
+    InvalidRoyaltySplit,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Soulbound passes cannot be listed")]
+    SoulboundPass,
+    #[msg("Revoked passes cannot be listed or sold")]
+    PassRevoked,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Passes cannot be transferred to their current owner")]
+    SelfTransfer,
+}
+
+// Charges a resale royalty, split between the vault and the creator like a primary sale
+fn pay_royalty<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    sol_vault: &AccountInfo<'info>,
+    creator_wallet: &AccountInfo<'info>,
+    creator_pool: &mut Account<'info, CreatorPool>,
+    royalty: u64,
+) -> Result<SaleSplit> {
+    let royalty_split = split_sale(royalty, 0, creator_pool.vault_share_bps)
+        .ok_or(ErrorCode::InvalidRoyaltySplit)?;
+
+    let transfers = [
+        (sol_vault.clone(), royalty_split.vault_amount),
+        (creator_wallet.clone(), royalty_split.creator_amount),
+    ];
+
+    for (to, amount) in transfers {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to,
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    creator_pool.total_deposited = creator_pool
+        .total_deposited
+        .checked_add(royalty_split.vault_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(royalty_split)
+}
+
+// Moves the escrowed pass out and closes the escrow, returning its rent to the seller
+fn release_escrow<'info>(
+    listing: &Account<'info, PassListing>,
+    escrow_token_account: &InterfaceAccount<'info, TokenAccount>,
+    pass_mint: &InterfaceAccount<'info, Mint>,
+    recipient_token_account: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    creator_collection: &Account<'info, CreatorCollection>,
+) -> Result<()> {
+    let listing_seeds = &[b"listing", listing.nft_mint.as_ref(), &[listing.bump]];
+    let signer = &[&listing_seeds[..]];
+
+    transfer_pass(
+        token_program,
+        pass_mint,
+        &escrow_token_account.to_account_info(),
+        recipient_token_account,
+        &listing.to_account_info(),
+        signer,
+        creator_collection,
+    )?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: seller.clone(),
+            authority: listing.to_account_info(),
+        },
+        signer,
+    ))
+}
+
+pub fn list_pass(ctx: Context<ListPass>, price: u64) -> Result<()> {
+    transfer_pass(
+        &ctx.accounts.token_program,
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.seller_token_account.to_account_info(),
+        &ctx.accounts.escrow_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &[],
+        &ctx.accounts.creator_collection,
+    )?;
+
+    let listing = &mut ctx.accounts.listing;
+    listing.seller = ctx.accounts.seller.key();
+    listing.nft_mint = ctx.accounts.pass_mint.key();
+    listing.creator_collection = ctx.accounts.nft_ownership.creator_collection;
+    listing.price = price;
+    listing.listed_at = Clock::get()?.unix_timestamp;
+    listing.bump = ctx.bumps.listing;
+
+    emit!(PassListed {
+        seller: listing.seller,
+        nft_mint: listing.nft_mint,
+        listing: listing.key(),
+        price,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    release_escrow(
+        &ctx.accounts.listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.seller_token_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.creator_collection,
+    )?;
+
+    emit!(PassListingCancelled {
+        seller: ctx.accounts.listing.seller,
+        nft_mint: ctx.accounts.listing.nft_mint,
+        listing: ctx.accounts.listing.key(),
+    });
+
+    Ok(())
+}
+
+pub fn buy_listed_pass(ctx: Context<BuyListedPass>, max_price: u64) -> Result<()> {
+    let price = ctx.accounts.listing.price;
+    let buyer = ctx.accounts.buyer.key();
+    require!(price <= max_price, ErrorCode::PriceAboveMax);
+    require_keys_neq!(buyer, ctx.accounts.listing.seller, ErrorCode::SelfPurchase);
+
+    let royalty = bps_of(price, ctx.accounts.creator_collection.royalty_bps)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let seller_amount = price.checked_sub(royalty).ok_or(ErrorCode::MathOverflow)?;
+
+    let royalty_split = pay_royalty(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator_wallet,
+        &mut ctx.accounts.creator_pool,
+        royalty,
+    )?;
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        seller_amount,
+    )?;
+
+    release_escrow(
+        &ctx.accounts.listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.buyer_token_account.to_account_info(),
+        &ctx.accounts.seller,
+        &ctx.accounts.token_program,
+        &ctx.accounts.creator_collection,
+    )?;
+
+    // Ownership moves in the same transaction as the token
+    let nft_ownership = &mut ctx.accounts.nft_ownership;
+    nft_ownership.owner = buyer;
+    nft_ownership.acquired_at = Clock::get()?.unix_timestamp;
+
+    emit!(PassSold {
+        seller: ctx.accounts.listing.seller,
+        buyer,
+        nft_mint: nft_ownership.nft_mint,
+        creator_collection: ctx.accounts.creator_collection.key(),
+        price,
+        royalty,
+        creator_pool_amount: royalty_split.vault_amount,
+        creator_amount: royalty_split.creator_amount,
+        seller_amount,
+    });
+
+    Ok(())
+}
+
+// Wallet transfers skip the marketplace, so the owner pays the royalty up front and the
+// transfer hook only lets the pass move to the approved recipient
+pub fn approve_pass_transfer(ctx: Context<ApprovePassTransfer>, recipient: Pubkey) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    require_keys_neq!(recipient, owner, ErrorCode::SelfTransfer);
+
+    // There is no sale price, so the royalty is taken on the collection's current price
+    let creator_collection = &ctx.accounts.creator_collection;
+    let price = creator_collection
+        .next_price()
+        .ok_or(ErrorCode::MathOverflow)?;
+    let royalty = bps_of(price, creator_collection.royalty_bps).ok_or(ErrorCode::MathOverflow)?;
+
+    let royalty_split = pay_royalty(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.sol_vault,
+        &ctx.accounts.creator_wallet,
+        &mut ctx.accounts.creator_pool,
+        royalty,
+    )?;
+
+    let transfer_approval = &mut ctx.accounts.transfer_approval;
+    transfer_approval.nft_mint = ctx.accounts.nft_ownership.nft_mint;
+    transfer_approval.owner = owner;
+    transfer_approval.recipient = recipient;
+    transfer_approval.royalty = royalty;
+    transfer_approval.approved_at = Clock::get()?.unix_timestamp;
+    transfer_approval.bump = ctx.bumps.transfer_approval;
+
+    emit!(PassTransferApproved {
+        owner,
+        recipient,
+        nft_mint: transfer_approval.nft_mint,
+        creator_collection: ctx.accounts.creator_collection.key(),
+        royalty,
+        creator_pool_amount: royalty_split.vault_amount,
+        creator_amount: royalty_split.creator_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,380 @@
+use crate::events::{
+    MisconductProposalCosigned, MisconductProposalFinalized, MisconductProposalOpened,
+    MisconductVoteCast, MisconductVotingStarted,
+};
+use crate::instructions::creator_pool::begin_pool_refund;
+use crate::instructions::fanpass::require_pass_holder;
+use crate::state::{
+    CreatorCollection, CreatorPool, Factory, MisconductProposal, NftOwnership, PoolStatus,
+    ProposalCosign, ProposalStatus, ProposalVote, VoteChoice,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct OpenMisconductProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = MisconductProposal::LEN,
+        seeds = [
+            b"misconduct",
+            creator_pool.key().as_ref(),
+            &creator_pool.misconduct_proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, MisconductProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_pool", creator_pool.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive
+    )]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalCosign::LEN,
+        seeds = [b"proposal_cosign", proposal.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub cosign: Account<'info, ProposalCosign>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub factory: Account<'info, Factory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CosignMisconductProposal<'info> {
+    #[account(
+        mut,
+        has_one = creator_pool,
+        constraint = proposal.status == ProposalStatus::Gathering @ ErrorCode::InvalidProposalStatus
+    )]
+    pub proposal: Account<'info, MisconductProposal>,
+
+    #[account(mut)]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    #[account(
+        init,
+        payer = cosigner,
+        space = ProposalCosign::LEN,
+        seeds = [b"proposal_cosign", proposal.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub cosign: Account<'info, ProposalCosign>,
+
+    #[account(mut)]
+    pub cosigner: Signer<'info>,
+
+    pub factory: Account<'info, Factory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteMisconductProposal<'info> {
+    #[account(
+        mut,
+        has_one = creator_pool,
+        constraint = proposal.status == ProposalStatus::Voting @ ErrorCode::InvalidProposalStatus,
+        constraint = Clock::get()?.unix_timestamp < proposal.voting_ends_at @ ErrorCode::VotingClosed
+    )]
+    pub proposal: Account<'info, MisconductProposal>,
+
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = ProposalVote::LEN,
+        seeds = [b"proposal_vote", proposal.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub proposal_vote: Account<'info, ProposalVote>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMisconductProposal<'info> {
+    #[account(
+        mut,
+        has_one = creator_pool,
+        constraint = proposal.status == ProposalStatus::Voting @ ErrorCode::InvalidProposalStatus,
+        constraint = Clock::get()?.unix_timestamp >= proposal.voting_ends_at @ ErrorCode::VotingStillActive
+    )]
+    pub proposal: Account<'info, MisconductProposal>,
+
+    #[account(mut)]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        constraint = creator_collection.creator == creator_pool.creator @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        seeds = [b"sol_vault", creator_pool.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid proposal status for this operation")]
+    InvalidProposalStatus,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Creator collection does not belong to this pool's creator")]
+    InvalidCreatorCollection,
+    #[msg("Evidence hash is too long")]
+    EvidenceTooLong,
+    #[msg("Voting has closed for this proposal")]
+    VotingClosed,
+    #[msg("Voting is still active")]
+    VotingStillActive,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Pass was acquired after the proposal was opened")]
+    PassAcquiredAfterProposal,
+}
+
+// Passes bought or transferred after opening can't be used to pile onto a proposal
+fn require_eligible_pass(
+    proposal: &MisconductProposal,
+    nft_ownership: &NftOwnership,
+) -> Result<()> {
+    require!(
+        nft_ownership.acquired_at <= proposal.created_at,
+        ErrorCode::PassAcquiredAfterProposal
+    );
+
+    Ok(())
+}
+
+// Opens the proposal for voting once enough passes have co-signed it
+fn start_voting_if_cosigned(
+    proposal: &mut Account<MisconductProposal>,
+    creator_pool: &mut CreatorPool,
+    factory: &Factory,
+) -> Result<()> {
+    if proposal.cosign_count < factory.misconduct_cosign_threshold {
+        return Ok(());
+    }
+
+    // Redemptions stay blocked until the vote is finalized
+    creator_pool.active_proposals = creator_pool
+        .active_proposals
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    proposal.status = ProposalStatus::Voting;
+    proposal.voting_ends_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(creator_pool.voting_window)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(MisconductVotingStarted {
+        proposal: proposal.key(),
+        creator_pool: proposal.creator_pool,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
+
+pub fn open_misconduct_proposal(
+    ctx: Context<OpenMisconductProposal>,
+    evidence_ipfs_hash: String,
+) -> Result<()> {
+    require!(evidence_ipfs_hash.len() <= 64, ErrorCode::EvidenceTooLong);
+
+    let creator_pool = &mut ctx.accounts.creator_pool;
+    let nft_ownership = &ctx.accounts.nft_ownership;
+    let proposer = ctx.accounts.proposer.key();
+
+    require_pass_holder(nft_ownership, &proposer, &creator_pool.creator)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.creator_pool = creator_pool.key();
+    proposal.proposer = proposer;
+    proposal.evidence_ipfs_hash = evidence_ipfs_hash;
+    proposal.status = ProposalStatus::Gathering;
+    proposal.cosign_count = 1;
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.voting_ends_at = 0;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+
+    // The proposer's own pass counts as the first co-signature
+    let cosign = &mut ctx.accounts.cosign;
+    cosign.proposal = proposal.key();
+    cosign.cosigner = proposer;
+    cosign.nft_mint = nft_ownership.nft_mint;
+    cosign.bump = ctx.bumps.cosign;
+
+    creator_pool.misconduct_proposal_count = creator_pool
+        .misconduct_proposal_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(MisconductProposalOpened {
+        proposal: proposal.key(),
+        creator_pool: proposal.creator_pool,
+        proposer,
+        evidence_ipfs_hash: proposal.evidence_ipfs_hash.clone(),
+    });
+
+    start_voting_if_cosigned(proposal, creator_pool, &ctx.accounts.factory)
+}
+
+pub fn cosign_misconduct_proposal(ctx: Context<CosignMisconductProposal>) -> Result<()> {
+    let nft_ownership = &ctx.accounts.nft_ownership;
+    let cosigner = ctx.accounts.cosigner.key();
+
+    require_pass_holder(nft_ownership, &cosigner, &ctx.accounts.creator_pool.creator)?;
+    require_eligible_pass(&ctx.accounts.proposal, nft_ownership)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.cosign_count = proposal
+        .cosign_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let cosign = &mut ctx.accounts.cosign;
+    cosign.proposal = proposal.key();
+    cosign.cosigner = cosigner;
+    cosign.nft_mint = nft_ownership.nft_mint;
+    cosign.bump = ctx.bumps.cosign;
+
+    emit!(MisconductProposalCosigned {
+        proposal: proposal.key(),
+        cosigner,
+        nft_mint: cosign.nft_mint,
+        cosign_count: proposal.cosign_count,
+    });
+
+    start_voting_if_cosigned(
+        proposal,
+        &mut ctx.accounts.creator_pool,
+        &ctx.accounts.factory,
+    )
+}
+
+pub fn vote_misconduct_proposal(
+    ctx: Context<VoteMisconductProposal>,
+    choice: VoteChoice,
+) -> Result<()> {
+    let nft_ownership = &ctx.accounts.nft_ownership;
+    let voter = ctx.accounts.voter.key();
+
+    require_pass_holder(nft_ownership, &voter, &ctx.accounts.creator_pool.creator)?;
+    require_eligible_pass(&ctx.accounts.proposal, nft_ownership)?;
+
+    // One vote per pass, recorded against the pass mint
+    let proposal = &mut ctx.accounts.proposal;
+    match choice {
+        VoteChoice::Yes => {
+            proposal.yes_votes = proposal
+                .yes_votes
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        VoteChoice::No => {
+            proposal.no_votes = proposal
+                .no_votes
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+    }
+
+    let proposal_vote = &mut ctx.accounts.proposal_vote;
+    proposal_vote.proposal = proposal.key();
+    proposal_vote.voter = voter;
+    proposal_vote.nft_mint = nft_ownership.nft_mint;
+    proposal_vote.vote_choice = choice.clone();
+    proposal_vote.voted_at = Clock::get()?.unix_timestamp;
+    proposal_vote.bump = ctx.bumps.proposal_vote;
+
+    emit!(MisconductVoteCast {
+        proposal: proposal.key(),
+        voter,
+        nft_mint: proposal_vote.nft_mint,
+        vote_choice: match choice {
+            VoteChoice::Yes => true,
+            VoteChoice::No => false,
+        },
+        voted_at: proposal_vote.voted_at,
+    });
+
+    Ok(())
+}
+
+pub fn finalize_misconduct_proposal(ctx: Context<FinalizeMisconductProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let creator_pool = &mut ctx.accounts.creator_pool;
+
+    creator_pool.active_proposals = creator_pool.active_proposals.saturating_sub(1);
+
+    let total_votes = proposal
+        .yes_votes
+        .checked_add(proposal.no_votes)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let passed = total_votes >= creator_pool.voting_quorum
+        && proposal.yes_votes > proposal.no_votes
+        && creator_pool.status == PoolStatus::Active;
+
+    if passed {
+        proposal.status = ProposalStatus::Passed;
+        begin_pool_refund(
+            creator_pool,
+            &ctx.accounts.sol_vault,
+            ctx.accounts.creator_collection.total_supply,
+        )?;
+    } else {
+        proposal.status = ProposalStatus::Failed;
+    }
+
+    emit!(MisconductProposalFinalized {
+        proposal: proposal.key(),
+        creator_pool: proposal.creator_pool,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        passed,
+    });
+
+    Ok(())
+}
@@ -4,6 +4,33 @@ pub use factory::*;
 pub mod fanpass;
 pub use fanpass::*;
 
+pub mod collection;
+pub use collection::*;
+
+pub mod referral;
+pub use referral::*;
+
+pub mod marketplace;
+pub use marketplace::*;
+
+pub mod redeem;
+pub use redeem::*;
+
+pub mod revoke;
+pub use revoke::*;
+
+pub mod transfer_hook;
+pub use transfer_hook::*;
+
+pub mod compressed_pass;
+pub use compressed_pass::*;
+
+pub mod core_pass;
+pub use core_pass::*;
+
+pub mod pass_ownership;
+pub use pass_ownership::*;
+
 pub mod creator_pool;
 pub use creator_pool::*;
 
@@ -12,3 +39,9 @@ pub use claims::*;
 
 pub mod voting;
 pub use voting::*;
+
+pub mod misconduct;
+pub use misconduct::*;
+
+pub mod treasury;
+pub use treasury::*;
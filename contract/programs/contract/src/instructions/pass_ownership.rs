@@ -0,0 +1,184 @@
+use crate::events::PassRegistered;
+use crate::instructions::compressed_pass::{verify_compressed_pass, CompressedPassProof};
+use crate::instructions::core_pass::{read_core_asset, ErrorCode as CoreError};
+use crate::instructions::fanpass::{
+    require_pass_holder, require_pass_token, ErrorCode as FanPassError, FanPassInfo,
+};
+use crate::state::{CreatorCollection, NftOwnership, RegisteredPass};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// Ownership checks shared by every kind of pass a creator collection can issue
+pub trait PassOwnership {
+    // Fails unless `holder` currently holds this pass and it belongs to the collection
+    fn verify(
+        &self,
+        holder: &Pubkey,
+        creator_collection: &Account<CreatorCollection>,
+    ) -> Result<()>;
+
+    fn tier(&self) -> u8 {
+        NftOwnership::TIER_PUBLIC
+    }
+
+    // Kinds without an on-chain purchase record report 0
+    fn acquired_at(&self) -> i64 {
+        0
+    }
+
+    // Zero means the pass never expires
+    fn valid_until(&self) -> i64 {
+        0
+    }
+}
+
+// Token-2022 pass minted by buy_pass, tracked by its nft_ownership PDA
+pub struct TokenPass<'a, 'info> {
+    pub nft_ownership: &'a NftOwnership,
+    pub token_account: &'a InterfaceAccount<'info, TokenAccount>,
+}
+
+impl PassOwnership for TokenPass<'_, '_> {
+    fn verify(
+        &self,
+        holder: &Pubkey,
+        creator_collection: &Account<CreatorCollection>,
+    ) -> Result<()> {
+        require_pass_holder(self.nft_ownership, holder, &creator_collection.creator)?;
+        require_keys_eq!(
+            self.nft_ownership.creator_collection,
+            creator_collection.key(),
+            FanPassError::InvalidNftCollection
+        );
+        require_pass_token(self.token_account, &self.nft_ownership.nft_mint, holder)
+    }
+
+    fn tier(&self) -> u8 {
+        self.nft_ownership.tier
+    }
+
+    fn acquired_at(&self) -> i64 {
+        self.nft_ownership.acquired_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.nft_ownership.valid_until
+    }
+}
+
+// Bubblegum leaf in the collection's registered tree, proven against the current root
+pub struct CompressedPass<'a, 'info> {
+    pub compression_program: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub proof_nodes: &'a [AccountInfo<'info>],
+    pub proof: &'a CompressedPassProof,
+}
+
+impl PassOwnership for CompressedPass<'_, '_> {
+    fn verify(
+        &self,
+        holder: &Pubkey,
+        creator_collection: &Account<CreatorCollection>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.merkle_tree.key(),
+            creator_collection.merkle_tree,
+            FanPassError::InvalidNftCollection
+        );
+        verify_compressed_pass(
+            self.compression_program,
+            self.merkle_tree,
+            self.proof_nodes,
+            holder,
+            self.proof,
+        )?;
+
+        Ok(())
+    }
+}
+
+// Metaplex Core asset whose update authority is the creator's Core collection
+pub struct CorePass<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+}
+
+impl PassOwnership for CorePass<'_, '_> {
+    fn verify(
+        &self,
+        holder: &Pubkey,
+        creator_collection: &Account<CreatorCollection>,
+    ) -> Result<()> {
+        require_keys_neq!(
+            creator_collection.core_collection,
+            Pubkey::default(),
+            CoreError::NoCoreCollection
+        );
+
+        let asset = read_core_asset(self.asset)?;
+        require_keys_eq!(asset.owner, *holder, CoreError::NotAssetOwner);
+        require!(
+            asset.collection == Some(creator_collection.core_collection),
+            CoreError::AssetNotInCollection
+        );
+
+        Ok(())
+    }
+}
+
+// Verifies any kind of pass and reports it in the common return-data shape
+pub fn fan_pass_info<P: PassOwnership>(
+    pass: &P,
+    holder: &Pubkey,
+    creator_collection: &Account<CreatorCollection>,
+) -> Result<FanPassInfo> {
+    pass.verify(holder, creator_collection)?;
+
+    Ok(FanPassInfo {
+        holder: *holder,
+        creator: creator_collection.creator,
+        tier: pass.tier(),
+        acquired_at: pass.acquired_at(),
+        valid_until: pass.valid_until(),
+    })
+}
+
+// Records the current holder of a compressed or Core pass. The first registration counts
+// the pass toward the collection's supply, and a new holder restarts its acquisition time.
+pub fn register_pass(
+    registered_pass: &mut Account<RegisteredPass>,
+    creator_collection: &mut Account<CreatorCollection>,
+    asset: Pubkey,
+    holder: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    if registered_pass.asset == Pubkey::default() {
+        // The creator can mint these passes at will, so they only count up to the cap
+        require!(
+            creator_collection.max_supply > 0,
+            FanPassError::UncappedCollection
+        );
+        require!(!creator_collection.is_sold_out(), FanPassError::SoldOut);
+        creator_collection.registered_passes = creator_collection
+            .registered_passes
+            .checked_add(1)
+            .ok_or(FanPassError::MathOverflow)?;
+
+        registered_pass.creator_collection = creator_collection.key();
+        registered_pass.asset = asset;
+        registered_pass.bump = bump;
+    } else if registered_pass.owner == holder {
+        return Ok(());
+    }
+
+    registered_pass.owner = holder;
+    registered_pass.acquired_at = Clock::get()?.unix_timestamp;
+
+    emit!(PassRegistered {
+        creator_collection: registered_pass.creator_collection,
+        asset,
+        owner: holder,
+        acquired_at: registered_pass.acquired_at,
+    });
+
+    Ok(())
+}
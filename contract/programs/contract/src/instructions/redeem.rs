@@ -0,0 +1,132 @@
+use crate::events::PassRedeemed;
+use crate::fees::{split_redemption, unreserved_vault_lamports};
+use crate::state::{CreatorCollection, CreatorPool, NftOwnership, PoolStatus};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RedeemPass<'info> {
+    #[account(
+        mut,
+        address = nft_ownership.creator_collection @ ErrorCode::InvalidCreatorCollection
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    // Redemptions would move the vault balance a claim is being voted on
+    #[account(
+        seeds = [b"creator_pool", creator_collection.creator.as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive,
+        constraint = creator_pool.active_claims == 0 @ ErrorCode::ClaimInVoting,
+        constraint = creator_pool.active_proposals == 0 @ ErrorCode::ProposalInVoting
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator_collection.creator.as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_ownership", pass_mint.key().as_ref()],
+        bump = nft_ownership.bump,
+        constraint = nft_ownership.owner == holder.key() @ ErrorCode::NotNftOwner,
+        constraint = !nft_ownership.revoked @ ErrorCode::PassRevoked,
+        close = holder
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    #[account(mut)]
+    pub pass_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = pass_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Not NFT owner")]
+    NotNftOwner,
+    #[msg("Pass does not belong to this creator collection")]
+    InvalidCreatorCollection,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Passes cannot be redeemed while a claim is being voted on")]
+    ClaimInVoting,
+    #[msg("Passes cannot be redeemed while a misconduct proposal is being voted on")]
+    ProposalInVoting,
+    #[msg("Vault has nothing to redeem")]
+    NothingToRedeem,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Revoked passes were already refunded")]
+    PassRevoked,
+}
+
+pub fn redeem_pass(ctx: Context<RedeemPass>) -> Result<()> {
+    let creator_collection = &ctx.accounts.creator_collection;
+
+    let unreserved = unreserved_vault_lamports(&ctx.accounts.sol_vault)?;
+    let redemption = split_redemption(
+        unreserved,
+        creator_collection.total_supply,
+        creator_collection.exit_fee_bps,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    require!(redemption.payout > 0, ErrorCode::NothingToRedeem);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.pass_mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    token_interface::close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.holder_token_account.to_account_info(),
+            destination: ctx.accounts.holder.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info(),
+        },
+    ))?;
+
+    ctx.accounts.sol_vault.sub_lamports(redemption.payout)?;
+    ctx.accounts.holder.add_lamports(redemption.payout)?;
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.total_supply = creator_collection
+        .total_supply
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PassRedeemed {
+        holder: ctx.accounts.holder.key(),
+        nft_mint: ctx.accounts.pass_mint.key(),
+        creator_collection: creator_collection.key(),
+        payout: redemption.payout,
+        exit_fee: redemption.exit_fee,
+        total_supply: creator_collection.total_supply,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::events::ReferrerRegistered;
+use crate::state::ReferrerStats;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = ReferrerStats::LEN,
+        seeds = [b"referrer_stats", referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer_stats = &mut ctx.accounts.referrer_stats;
+    referrer_stats.referrer = ctx.accounts.referrer.key();
+    referrer_stats.referral_count = 0;
+    referrer_stats.total_earned = 0;
+    referrer_stats.bump = ctx.bumps.referrer_stats;
+
+    emit!(ReferrerRegistered {
+        referrer: referrer_stats.referrer,
+        referrer_stats: referrer_stats.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::events::PassRevoked;
+use crate::fees::{revocation_refund, unreserved_vault_lamports};
+use crate::state::{CreatorCollection, CreatorPool, Factory, NftOwnership, PoolStatus};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokePass<'info> {
+    #[account(
+        mut,
+        seeds = [b"creator_collection", creator.key().as_ref()],
+        bump = creator_collection.bump,
+        has_one = creator
+    )]
+    pub creator_collection: Box<Account<'info, CreatorCollection>>,
+
+    // Revoking during a vote would let the creator remove voters from their own claim
+    #[account(
+        seeds = [b"creator_pool", creator.key().as_ref()],
+        bump = creator_pool.bump,
+        constraint = creator_pool.status == PoolStatus::Active @ ErrorCode::PoolNotActive,
+        constraint = creator_pool.active_claims == 0 @ ErrorCode::ClaimInVoting,
+        constraint = creator_pool.active_proposals == 0 @ ErrorCode::ProposalInVoting
+    )]
+    pub creator_pool: Box<Account<'info, CreatorPool>>,
+
+    /// CHECK: This is the SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"sol_vault", creator.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump
+    )]
+    pub factory: Box<Account<'info, Factory>>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump,
+        constraint = nft_ownership.creator_collection == creator_collection.key() @ ErrorCode::InvalidCreatorCollection,
+        constraint = !nft_ownership.revoked @ ErrorCode::AlreadyRevoked
+    )]
+    pub nft_ownership: Box<Account<'info, NftOwnership>>,
+
+    /// CHECK: Receives the refund, must be the pass's current owner
+    #[account(
+        mut,
+        address = nft_ownership.owner @ ErrorCode::InvalidHolder
+    )]
+    pub holder: AccountInfo<'info>,
+
+    pub creator: Signer<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Pass does not belong to this creator collection")]
+    InvalidCreatorCollection,
+    #[msg("Creator pool is not active")]
+    PoolNotActive,
+    #[msg("Passes cannot be revoked while a claim is being voted on")]
+    ClaimInVoting,
+    #[msg("Passes cannot be revoked while a misconduct proposal is being voted on")]
+    ProposalInVoting,
+    #[msg("Pass has already been revoked")]
+    AlreadyRevoked,
+    #[msg("Refund recipient is not the pass holder")]
+    InvalidHolder,
+    #[msg("Revocation cooldown has not elapsed")]
+    RevocationCooldown,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
+
+pub fn revoke_pass(ctx: Context<RevokePass>, reason_hash: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let creator_collection = &ctx.accounts.creator_collection;
+    if creator_collection.last_revoked_at > 0 {
+        let cooldown_ends_at = creator_collection
+            .last_revoked_at
+            .checked_add(ctx.accounts.factory.revocation_cooldown)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(now >= cooldown_ends_at, ErrorCode::RevocationCooldown);
+    }
+
+    let unreserved = unreserved_vault_lamports(&ctx.accounts.sol_vault)?;
+    let refund = revocation_refund(
+        unreserved,
+        creator_collection.total_supply,
+        ctx.accounts.nft_ownership.vault_contribution,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts.sol_vault.sub_lamports(refund)?;
+    ctx.accounts.holder.add_lamports(refund)?;
+
+    let nft_ownership = &mut ctx.accounts.nft_ownership;
+    nft_ownership.revoked = true;
+
+    let creator_collection = &mut ctx.accounts.creator_collection;
+    creator_collection.last_revoked_at = now;
+    creator_collection.total_supply = creator_collection
+        .total_supply
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(PassRevoked {
+        creator: creator_collection.creator,
+        holder: nft_ownership.owner,
+        nft_mint: nft_ownership.nft_mint,
+        creator_collection: creator_collection.key(),
+        refund,
+        reason_hash,
+        revoked_at: now,
+    });
+
+    Ok(())
+}
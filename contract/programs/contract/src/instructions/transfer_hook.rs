@@ -0,0 +1,233 @@
+use crate::events::PassOwnershipSynced;
+use crate::state::{CreatorCollection, NftOwnership, TransferApproval};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::{transfer_hook_update, TransferHookUpdate};
+use anchor_spl::token_interface::spl_token_2022::extension::transfer_hook::{
+    self, TransferHookAccount,
+};
+use anchor_spl::token_interface::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_interface::spl_token_2022::state::{
+    Account as TokenAccountState, Mint as MintState,
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+// Token-2022 invokes this on every pass transfer with the accounts listed in the
+// mint's extra-account-metas PDA: [source, mint, destination, owner, metas, nft_ownership,
+// creator_collection, transfer_approval]. Program transfers suspend the hook, so anything
+// reaching it is a wallet transfer.
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner or delegate of the source account, already verified by the token program
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: The mint's extra-account-metas PDA
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"nft_ownership", mint.key().as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    #[account(address = nft_ownership.creator_collection)]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Left uninitialized until the owner approves a transfer, so it is loaded in the handler
+    #[account(
+        mut,
+        seeds = [b"transfer_approval", mint.key().as_ref()],
+        bump
+    )]
+    pub transfer_approval: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Transfer hook can only be invoked by the token program during a transfer")]
+    NotTransferring,
+    #[msg("Wallet transfers of royalty passes need an approval with the royalty paid")]
+    TransferNotApproved,
+}
+
+pub fn extra_account_metas_len() -> usize {
+    ExtraAccountMetaList::size_of(3).unwrap()
+}
+
+// Points the hook at the pass's nft_ownership and transfer_approval PDAs, both seeded by
+// the mint (account index 1), and at the collection whose royalty applies
+pub fn init_extra_account_metas(
+    extra_account_meta_list: &AccountInfo,
+    creator_collection: &Pubkey,
+) -> Result<()> {
+    let extra_metas = [
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"nft_ownership".to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_pubkey(creator_collection, false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"transfer_approval".to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+    ];
+
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut extra_account_meta_list.try_borrow_mut_data()?,
+        &extra_metas,
+    )?;
+
+    Ok(())
+}
+
+// Token-2022 cannot call back into this program while it is already on the call stack,
+// so program-initiated transfers suspend the hook and keep nft_ownership in sync themselves
+pub fn transfer_pass<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    authority_seeds: &[&[&[u8]]],
+    creator_collection: &Account<'info, CreatorCollection>,
+) -> Result<()> {
+    let has_hook = {
+        let mint_data = mint.to_account_info().try_borrow_data()?.to_vec();
+        let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+        transfer_hook::get_program_id(&mint_state).is_some()
+    };
+
+    let collection_seeds = &[
+        b"creator_collection",
+        creator_collection.creator.as_ref(),
+        &[creator_collection.bump],
+    ];
+    let collection_signer = &[&collection_seeds[..]];
+    let set_hook = |program_id: Option<Pubkey>| {
+        transfer_hook_update(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferHookUpdate {
+                    token_program_id: token_program.to_account_info(),
+                    mint: mint.to_account_info(),
+                    authority: creator_collection.to_account_info(),
+                },
+                collection_signer,
+            ),
+            program_id,
+        )
+    };
+
+    if has_hook {
+        set_hook(None)?;
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.clone(),
+                mint: mint.to_account_info(),
+                to: to.clone(),
+                authority: authority.clone(),
+            },
+            authority_seeds,
+        ),
+        1,
+        mint.decimals,
+    )?;
+
+    if has_hook {
+        set_hook(Some(crate::ID))?;
+    }
+
+    Ok(())
+}
+
+pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+    // Reject direct calls that would let anyone rewrite ownership without moving the token
+    let transferring = {
+        let source_data = ctx
+            .accounts
+            .source_token
+            .to_account_info()
+            .try_borrow_data()?
+            .to_vec();
+        let source_state = StateWithExtensions::<TokenAccountState>::unpack(&source_data)?;
+        let hook_account = source_state.get_extension::<TransferHookAccount>()?;
+        bool::from(hook_account.transferring)
+    };
+    require!(transferring, ErrorCode::NotTransferring);
+
+    let nft_ownership = &mut ctx.accounts.nft_ownership;
+    let new_owner = ctx.accounts.destination_token.owner;
+
+    // A plain transfer_checked would otherwise skip the royalty that buy_listed_pass charges
+    if ctx.accounts.creator_collection.royalty_bps > 0 {
+        let approval_info = &ctx.accounts.transfer_approval;
+        require_keys_eq!(
+            *approval_info.owner,
+            crate::ID,
+            ErrorCode::TransferNotApproved
+        );
+        let mut approval =
+            TransferApproval::try_deserialize(&mut &approval_info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            approval.owner,
+            nft_ownership.owner,
+            ErrorCode::TransferNotApproved
+        );
+        require_keys_eq!(
+            approval.recipient,
+            new_owner,
+            ErrorCode::TransferNotApproved
+        );
+
+        // Each paid royalty covers a single transfer
+        approval.recipient = Pubkey::default();
+        approval.try_serialize(&mut &mut approval_info.try_borrow_mut_data()?[..])?;
+    }
+
+    let previous_owner = nft_ownership.owner;
+    nft_ownership.owner = new_owner;
+    nft_ownership.acquired_at = Clock::get()?.unix_timestamp;
+
+    emit!(PassOwnershipSynced {
+        nft_mint: nft_ownership.nft_mint,
+        previous_owner,
+        new_owner,
+        acquired_at: nft_ownership.acquired_at,
+    });
+
+    Ok(())
+}
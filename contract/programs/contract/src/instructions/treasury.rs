@@ -0,0 +1,100 @@
+use crate::events::{TreasuryInitialized, TreasuryWithdrawn};
+use crate::state::{Factory, Treasury};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::LEN,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority
+    )]
+    pub factory: Account<'info, Factory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        seeds = [b"factory"],
+        bump = factory.bump,
+        has_one = authority
+    )]
+    pub factory: Account<'info, Factory>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: This is a regular account that can receive native SOL
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+}
+
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.factory = ctx.accounts.factory.key();
+    treasury.total_collected = 0;
+    treasury.total_withdrawn = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    emit!(TreasuryInitialized {
+        treasury: treasury.key(),
+        factory: treasury.factory,
+    });
+
+    Ok(())
+}
+
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+
+    // The treasury keeps its rent-exempt minimum
+    let rent_minimum = Rent::get()?.minimum_balance(Treasury::LEN);
+    let available = treasury.get_lamports().saturating_sub(rent_minimum);
+    require!(amount <= available, ErrorCode::InsufficientFunds);
+
+    treasury.sub_lamports(amount)?;
+    ctx.accounts.recipient.add_lamports(amount)?;
+
+    treasury.total_withdrawn = treasury
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(TreasuryWithdrawn {
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        total_collected: treasury.total_collected,
+        total_withdrawn: treasury.total_withdrawn,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,14 @@
-use crate::events::VoteCast;
-use crate::state::{Claim, VoteAccount, VoteChoice};
+use crate::events::{VoteCast, VotingExtended};
+use crate::instructions::compressed_pass::{
+    compressed_asset_id, CompressedPassProof, ACCOUNT_COMPRESSION_PROGRAM_ID,
+};
+use crate::instructions::pass_ownership::{CompressedPass, CorePass, PassOwnership, TokenPass};
+use crate::state::{
+    Claim, CreatorCollection, CreatorPool, NftOwnership, RegisteredPass, VoteAccount, VoteChoice,
+};
 // use anchor_lang::init_if_needed;
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 #[derive(Accounts)]
 pub struct Vote<'info> {
@@ -9,17 +16,133 @@ pub struct Vote<'info> {
         mut,
         has_one = creator_pool,
         constraint = (claim.status == crate::state::ClaimStatus::Voting || claim.status == crate::state::ClaimStatus::Pending) @ ErrorCode::InvalidClaimStatus,
+        constraint = Clock::get()?.unix_timestamp < claim.voting_ends_at @ ErrorCode::VotingClosed,
     )]
     pub claim: Account<'info, Claim>,
 
     #[account(mut)]
-    pub creator_pool: Account<'info, crate::state::CreatorPool>,
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_pool.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    pub fan_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // One vote per pass, so moving a pass to another wallet cannot vote twice
+    #[account(
+        init_if_needed,
+        payer = fan,
+        space = VoteAccount::LEN,
+        seeds = [b"vote", claim.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+
+    #[account(mut)]
+    pub fan: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(choice: VoteChoice, proof: CompressedPassProof)]
+pub struct VoteWithCompressedPass<'info> {
+    #[account(
+        mut,
+        has_one = creator_pool,
+        constraint = (claim.status == crate::state::ClaimStatus::Voting || claim.status == crate::state::ClaimStatus::Pending) @ ErrorCode::InvalidClaimStatus,
+        constraint = Clock::get()?.unix_timestamp < claim.voting_ends_at @ ErrorCode::VotingClosed,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut)]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_pool.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Must be the tree registered on the creator collection
+    #[account(address = creator_collection.merkle_tree @ ErrorCode::InvalidMerkleTree)]
+    pub merkle_tree: AccountInfo<'info>,
+
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: AccountInfo<'info>,
+
+    #[account(
+        seeds = [
+            b"registered_pass",
+            compressed_asset_id(&merkle_tree.key(), proof.nonce).as_ref()
+        ],
+        bump = registered_pass.bump
+    )]
+    pub registered_pass: Account<'info, RegisteredPass>,
+
+    // Compressed passes vote once per asset id, like minted passes vote once per mint
+    #[account(
+        init_if_needed,
+        payer = fan,
+        space = VoteAccount::LEN,
+        seeds = [
+            b"vote",
+            claim.key().as_ref(),
+            compressed_asset_id(&merkle_tree.key(), proof.nonce).as_ref()
+        ],
+        bump
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+
+    #[account(mut)]
+    pub fan: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteWithCorePass<'info> {
+    #[account(
+        mut,
+        has_one = creator_pool,
+        constraint = (claim.status == crate::state::ClaimStatus::Voting || claim.status == crate::state::ClaimStatus::Pending) @ ErrorCode::InvalidClaimStatus,
+        constraint = Clock::get()?.unix_timestamp < claim.voting_ends_at @ ErrorCode::VotingClosed,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut)]
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_pool.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    /// CHECK: Metaplex Core asset, parsed by CorePass
+    pub asset: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"registered_pass", asset.key().as_ref()],
+        bump = registered_pass.bump
+    )]
+    pub registered_pass: Account<'info, RegisteredPass>,
 
     #[account(
         init_if_needed,
         payer = fan,
         space = VoteAccount::LEN,
-        seeds = [b"vote", claim.key().as_ref(), fan.key().as_ref()],
+        seeds = [b"vote", claim.key().as_ref(), asset.key().as_ref()],
         bump
     )]
     pub vote_account: Account<'info, VoteAccount>,
@@ -36,15 +159,32 @@ pub struct ChangeVote<'info> {
         mut,
         has_one = creator_pool,
         constraint = (claim.status == crate::state::ClaimStatus::Voting || claim.status == crate::state::ClaimStatus::Pending) @ ErrorCode::InvalidClaimStatus,
+        constraint = Clock::get()?.unix_timestamp < claim.voting_ends_at @ ErrorCode::VotingClosed,
     )]
     pub claim: Account<'info, Claim>,
 
     #[account(mut)]
-    pub creator_pool: Account<'info, crate::state::CreatorPool>,
+    pub creator_pool: Account<'info, CreatorPool>,
+
+    #[account(
+        seeds = [b"creator_collection", creator_pool.creator.as_ref()],
+        bump = creator_collection.bump
+    )]
+    pub creator_collection: Account<'info, CreatorCollection>,
+
+    #[account(
+        seeds = [b"nft_ownership", nft_ownership.nft_mint.as_ref()],
+        bump = nft_ownership.bump
+    )]
+    pub nft_ownership: Account<'info, NftOwnership>,
+
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         has_one = voter,
+        seeds = [b"vote", claim.key().as_ref(), nft_ownership.nft_mint.as_ref()],
+        bump = vote_account.bump,
         constraint = vote_account.claim == claim.key() @ ErrorCode::InvalidVoteAccount
     )]
     pub vote_account: Account<'info, VoteAccount>,
@@ -60,11 +200,74 @@ pub enum ErrorCode {
     InvalidVoteAccount,
     #[msg("Math overflow")]
     MathOverflow,
+    #[msg("Voting has closed for this claim")]
+    VotingClosed,
+    #[msg("Pass was acquired after the claim was filed")]
+    PassAcquiredAfterClaim,
+    #[msg("Merkle tree is not the creator's compressed pass tree")]
+    InvalidMerkleTree,
+    #[msg("Pass must be registered to its current holder before voting")]
+    PassNotRegistered,
 }
 
-pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
-    let claim = &mut ctx.accounts.claim;
-    let vote_account = &mut ctx.accounts.vote_account;
+// Extends the voting window when a vote inside the claim's extension window flips the result
+fn extend_voting_if_flipped(claim: &mut Account<Claim>, was_passing: bool, now: i64) -> Result<()> {
+    let extension = claim.vote_extension(was_passing, now);
+    if extension == 0 {
+        return Ok(());
+    }
+
+    let previous_ends_at = claim.voting_ends_at;
+    claim.voting_ends_at = claim
+        .voting_ends_at
+        .checked_add(extension)
+        .ok_or(ErrorCode::MathOverflow)?;
+    claim.vote_extension_total = claim
+        .vote_extension_total
+        .checked_add(extension)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(VotingExtended {
+        claim: claim.key(),
+        creator_pool: claim.creator_pool,
+        previous_ends_at,
+        voting_ends_at: claim.voting_ends_at,
+        vote_extension_total: claim.vote_extension_total,
+    });
+
+    Ok(())
+}
+
+// Passes bought or transferred after filing are outside the claim's eligible supply
+fn require_eligible_pass(claim: &Claim, acquired_at: i64) -> Result<()> {
+    require!(
+        acquired_at <= claim.created_at,
+        ErrorCode::PassAcquiredAfterClaim
+    );
+
+    Ok(())
+}
+
+// Compressed and Core passes vote through their registration record, which must already
+// name the wallet that holds the pass now
+fn require_eligible_registered_pass(
+    claim: &Claim,
+    registered_pass: &RegisteredPass,
+    holder: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(registered_pass.owner, *holder, ErrorCode::PassNotRegistered);
+    require_eligible_pass(claim, registered_pass.acquired_at)
+}
+
+// Records a pass's vote on a claim, replacing any earlier choice made with the same pass
+fn cast_vote(
+    claim: &mut Account<Claim>,
+    vote_account: &mut Account<VoteAccount>,
+    voter: Pubkey,
+    choice: VoteChoice,
+    bump: u8,
+) -> Result<()> {
+    let was_passing = claim.yes_votes > claim.no_votes;
 
     // Check if this is a new vote or changing an existing vote
     let is_new_vote = vote_account.vote_choice.is_none();
@@ -72,10 +275,10 @@ pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
 
     // Update vote account
     vote_account.claim = claim.key();
-    vote_account.voter = ctx.accounts.fan.key();
+    vote_account.voter = voter;
     vote_account.vote_choice = Some(choice.clone());
     vote_account.voted_at = Clock::get()?.unix_timestamp;
-    vote_account.bump = ctx.bumps.vote_account;
+    vote_account.bump = bump;
 
     // Update claim vote counts
     if is_new_vote {
@@ -129,10 +332,12 @@ pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
         }
     }
 
+    extend_voting_if_flipped(claim, was_passing, vote_account.voted_at)?;
+
     // Emit event
     emit!(VoteCast {
         claim: claim.key(),
-        voter: ctx.accounts.fan.key(),
+        voter,
         vote_choice: match choice {
             VoteChoice::Yes => true,
             VoteChoice::No => false,
@@ -143,9 +348,77 @@ pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
     Ok(())
 }
 
+pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
+    let fan = ctx.accounts.fan.key();
+    TokenPass {
+        nft_ownership: &ctx.accounts.nft_ownership,
+        token_account: &ctx.accounts.fan_token_account,
+    }
+    .verify(&fan, &ctx.accounts.creator_collection)?;
+    require_eligible_pass(&ctx.accounts.claim, ctx.accounts.nft_ownership.acquired_at)?;
+
+    cast_vote(
+        &mut ctx.accounts.claim,
+        &mut ctx.accounts.vote_account,
+        fan,
+        choice,
+        ctx.bumps.vote_account,
+    )
+}
+
+pub fn vote_with_compressed_pass<'info>(
+    ctx: Context<'_, '_, '_, 'info, VoteWithCompressedPass<'info>>,
+    choice: VoteChoice,
+    proof: CompressedPassProof,
+) -> Result<()> {
+    let fan = ctx.accounts.fan.key();
+    CompressedPass {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        proof_nodes: ctx.remaining_accounts,
+        proof: &proof,
+    }
+    .verify(&fan, &ctx.accounts.creator_collection)?;
+    require_eligible_registered_pass(&ctx.accounts.claim, &ctx.accounts.registered_pass, &fan)?;
+
+    cast_vote(
+        &mut ctx.accounts.claim,
+        &mut ctx.accounts.vote_account,
+        fan,
+        choice,
+        ctx.bumps.vote_account,
+    )
+}
+
+pub fn vote_with_core_pass(ctx: Context<VoteWithCorePass>, choice: VoteChoice) -> Result<()> {
+    let fan = ctx.accounts.fan.key();
+    CorePass {
+        asset: &ctx.accounts.asset,
+    }
+    .verify(&fan, &ctx.accounts.creator_collection)?;
+    require_eligible_registered_pass(&ctx.accounts.claim, &ctx.accounts.registered_pass, &fan)?;
+
+    cast_vote(
+        &mut ctx.accounts.claim,
+        &mut ctx.accounts.vote_account,
+        fan,
+        choice,
+        ctx.bumps.vote_account,
+    )
+}
+
 pub fn change_vote(ctx: Context<ChangeVote>, new_choice: VoteChoice) -> Result<()> {
+    let voter = ctx.accounts.voter.key();
+    TokenPass {
+        nft_ownership: &ctx.accounts.nft_ownership,
+        token_account: &ctx.accounts.voter_token_account,
+    }
+    .verify(&voter, &ctx.accounts.creator_collection)?;
+    require_eligible_pass(&ctx.accounts.claim, ctx.accounts.nft_ownership.acquired_at)?;
+
     let claim = &mut ctx.accounts.claim;
     let vote_account = &mut ctx.accounts.vote_account;
+    let was_passing = claim.yes_votes > claim.no_votes;
 
     // Get the old choice
     let old_choice = vote_account
@@ -188,6 +461,8 @@ pub fn change_vote(ctx: Context<ChangeVote>, new_choice: VoteChoice) -> Result<(
         }
     }
 
+    extend_voting_if_flipped(claim, was_passing, vote_account.voted_at)?;
+
     // Emit event
     emit!(VoteCast {
         claim: claim.key(),
@@ -2,10 +2,15 @@ use anchor_lang::prelude::*;
 
 declare_id!("BqHTWrkNFvj9ZA24yFkcTiXdczrNuQpspknnt3tWabVF");
 pub mod events;
+pub mod fees;
 pub mod instructions;
+pub mod merkle;
+pub mod pricing;
 pub mod state;
 use crate::instructions::*;
 use crate::state::VoteChoice;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
 #[program]
 pub mod contract {
@@ -15,22 +20,70 @@ pub mod contract {
         ctx: Context<InitializeFactory>,
         default_quorum: u64,
         default_voting_window: i64,
-        platform_fee_percentage: u64,
+        platform_fee_bps: u64,
+        max_platform_fee_bps: u64,
     ) -> Result<()> {
         instructions::initialize_factory(
             ctx,
             default_quorum,
             default_voting_window,
-            platform_fee_percentage,
+            platform_fee_bps,
+            max_platform_fee_bps,
         )
     }
 
+    pub fn update_factory(ctx: Context<UpdateFactory>, params: UpdateFactoryParams) -> Result<()> {
+        instructions::update_factory(ctx, params)
+    }
+
+    pub fn set_creator_fee_override(
+        ctx: Context<SetCreatorFeeOverride>,
+        creator: Pubkey,
+        platform_fee_bps: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::set_creator_fee_override(ctx, creator, platform_fee_bps, expires_at)
+    }
+
+    pub fn remove_creator_fee_override(ctx: Context<RemoveCreatorFeeOverride>) -> Result<()> {
+        instructions::remove_creator_fee_override(ctx)
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::initialize_treasury(ctx)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury(ctx, amount)
+    }
+
     pub fn create_pool(
         ctx: Context<CreateCreatorPool>,
         voting_quorum: u64,
         voting_window: i64,
+        vault_share_bps: u64,
+    ) -> Result<()> {
+        instructions::create_pool(ctx, voting_quorum, voting_window, vault_share_bps)
+    }
+
+    pub fn propose_vault_share(
+        ctx: Context<ProposeVaultShare>,
+        vault_share_bps: u64,
+    ) -> Result<()> {
+        instructions::propose_vault_share(ctx, vault_share_bps)
+    }
+
+    pub fn apply_vault_share(ctx: Context<ApplyVaultShare>) -> Result<()> {
+        instructions::apply_vault_share(ctx)
+    }
+
+    pub fn set_vote_extension(
+        ctx: Context<SetVoteExtension>,
+        extension_window: i64,
+        extension_duration: i64,
+        max_extension: i64,
     ) -> Result<()> {
-        instructions::create_pool(ctx, voting_quorum, voting_window)
+        instructions::set_vote_extension(ctx, extension_window, extension_duration, max_extension)
     }
 
     pub fn deposit_to_pool(ctx: Context<DepositToPool>, amount: u64) -> Result<()> {
@@ -41,6 +94,18 @@ pub mod contract {
         instructions::withdraw_from_pool(ctx, amount)
     }
 
+    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        instructions::heartbeat(ctx)
+    }
+
+    pub fn trigger_inactivity_refund(ctx: Context<TriggerInactivityRefund>) -> Result<()> {
+        instructions::trigger_inactivity_refund(ctx)
+    }
+
+    pub fn withdraw_pool_refund(ctx: Context<WithdrawPoolRefund>) -> Result<()> {
+        instructions::withdraw_pool_refund(ctx)
+    }
+
     pub fn deposit_from_nft_sale(
         ctx: Context<DepositFromNftSale>,
         total_amount: u64,
@@ -48,10 +113,95 @@ pub mod contract {
         instructions::deposit_from_nft_sale(ctx, total_amount)
     }
 
-    pub fn verify_fan_pass(ctx: Context<VerifyFanPass>) -> Result<()> {
+    pub fn create_collection(
+        ctx: Context<CreateCollection>,
+        params: CreateCollectionParams,
+    ) -> Result<()> {
+        instructions::create_collection(ctx, params)
+    }
+
+    pub fn set_presale(ctx: Context<SetPresale>, params: SetPresaleParams) -> Result<()> {
+        instructions::set_presale(ctx, params)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer(ctx)
+    }
+
+    pub fn buy_pass(ctx: Context<BuyPass>, max_price: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::buy_pass(ctx, max_price, proof)
+    }
+
+    pub fn redeem_pass(ctx: Context<RedeemPass>) -> Result<()> {
+        instructions::redeem_pass(ctx)
+    }
+
+    pub fn revoke_pass(ctx: Context<RevokePass>, reason_hash: [u8; 32]) -> Result<()> {
+        instructions::revoke_pass(ctx, reason_hash)
+    }
+
+    pub fn list_pass(ctx: Context<ListPass>, price: u64) -> Result<()> {
+        instructions::list_pass(ctx, price)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing(ctx)
+    }
+
+    pub fn buy_listed_pass(ctx: Context<BuyListedPass>, max_price: u64) -> Result<()> {
+        instructions::buy_listed_pass(ctx, max_price)
+    }
+
+    pub fn approve_pass_transfer(
+        ctx: Context<ApprovePassTransfer>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_pass_transfer(ctx, recipient)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::transfer_hook(ctx, amount)
+    }
+
+    pub fn renew_pass(ctx: Context<RenewPass>, max_price: u64) -> Result<()> {
+        instructions::renew_pass(ctx, max_price)
+    }
+
+    pub fn verify_fan_pass(ctx: Context<VerifyFanPass>) -> Result<FanPassInfo> {
         instructions::verify_fan_pass(ctx)
     }
 
+    pub fn set_compressed_tree(ctx: Context<SetCompressedTree>) -> Result<()> {
+        instructions::set_compressed_tree(ctx)
+    }
+
+    pub fn verify_compressed_fan_pass<'info>(
+        ctx: Context<'_, '_, '_, 'info, VerifyCompressedFanPass<'info>>,
+        proof: CompressedPassProof,
+    ) -> Result<FanPassInfo> {
+        instructions::verify_compressed_fan_pass(ctx, proof)
+    }
+
+    pub fn register_compressed_pass<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterCompressedPass<'info>>,
+        proof: CompressedPassProof,
+    ) -> Result<()> {
+        instructions::register_compressed_pass(ctx, proof)
+    }
+
+    pub fn set_core_collection(ctx: Context<SetCoreCollection>) -> Result<()> {
+        instructions::set_core_collection(ctx)
+    }
+
+    pub fn verify_core_fan_pass(ctx: Context<VerifyCoreFanPass>) -> Result<FanPassInfo> {
+        instructions::verify_core_fan_pass(ctx)
+    }
+
+    pub fn register_core_pass(ctx: Context<RegisterCorePass>) -> Result<()> {
+        instructions::register_core_pass(ctx)
+    }
+
     pub fn file_claim(
         ctx: Context<FileClaim>,
         evidence_ipfs_hash: String,
@@ -87,10 +237,44 @@ pub mod contract {
         instructions::vote(ctx, choice)
     }
 
+    pub fn vote_with_compressed_pass<'info>(
+        ctx: Context<'_, '_, '_, 'info, VoteWithCompressedPass<'info>>,
+        choice: VoteChoice,
+        proof: CompressedPassProof,
+    ) -> Result<()> {
+        instructions::vote_with_compressed_pass(ctx, choice, proof)
+    }
+
+    pub fn vote_with_core_pass(ctx: Context<VoteWithCorePass>, choice: VoteChoice) -> Result<()> {
+        instructions::vote_with_core_pass(ctx, choice)
+    }
+
     pub fn change_vote(ctx: Context<ChangeVote>, new_choice: VoteChoice) -> Result<()> {
         instructions::change_vote(ctx, new_choice)
     }
 
+    pub fn open_misconduct_proposal(
+        ctx: Context<OpenMisconductProposal>,
+        evidence_ipfs_hash: String,
+    ) -> Result<()> {
+        instructions::open_misconduct_proposal(ctx, evidence_ipfs_hash)
+    }
+
+    pub fn cosign_misconduct_proposal(ctx: Context<CosignMisconductProposal>) -> Result<()> {
+        instructions::cosign_misconduct_proposal(ctx)
+    }
+
+    pub fn vote_misconduct_proposal(
+        ctx: Context<VoteMisconductProposal>,
+        choice: VoteChoice,
+    ) -> Result<()> {
+        instructions::vote_misconduct_proposal(ctx, choice)
+    }
+
+    pub fn finalize_misconduct_proposal(ctx: Context<FinalizeMisconductProposal>) -> Result<()> {
+        instructions::finalize_misconduct_proposal(ctx)
+    }
+
     pub fn distribute_to_nft_holder(
         ctx: Context<DistributeToNftHolder>,
         amount: u64,
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::Pubkey;
+use solana_keccak_hasher::hashv;
+
+// Leaves and inner nodes are hashed with distinct prefixes so one can never pass for the other
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+// Pairs are sorted before hashing so proofs do not need to carry left/right positions
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == *root
+}
+
+// Hashes one tree level into the next, carrying an unpaired last node up unchanged
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => hash_pair(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+// Builds the allowlist root for a list of wallets, used off-chain when configuring a presale
+pub fn root_for(wallets: &[Pubkey]) -> Option<[u8; 32]> {
+    let mut level: Vec<[u8; 32]> = wallets.iter().map(leaf).collect();
+    if level.is_empty() {
+        return None;
+    }
+
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+
+    Some(level[0])
+}
+
+// Builds the proof a wallet submits with its presale purchase
+pub fn proof_for(wallets: &[Pubkey], wallet: &Pubkey) -> Option<Vec<[u8; 32]>> {
+    let mut index = wallets.iter().position(|w| w == wallet)?;
+    let mut level: Vec<[u8; 32]> = wallets.iter().map(leaf).collect();
+    let mut proof = Vec::new();
+
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    Some(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallets(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn every_listed_wallet_has_a_valid_proof() {
+        for count in 1..=9 {
+            let list = wallets(count);
+            let root = root_for(&list).unwrap();
+            for wallet in list.iter() {
+                let proof = proof_for(&list, wallet).unwrap();
+                assert!(verify(&proof, &root, leaf(wallet)));
+            }
+        }
+    }
+
+    #[test]
+    fn single_wallet_root_is_its_leaf() {
+        let list = wallets(1);
+        assert_eq!(root_for(&list), Some(leaf(&list[0])));
+        assert_eq!(proof_for(&list, &list[0]), Some(vec![]));
+    }
+
+    #[test]
+    fn rejects_unlisted_wallet() {
+        let list = wallets(5);
+        let root = root_for(&list).unwrap();
+        let outsider = Pubkey::new_unique();
+
+        assert_eq!(proof_for(&list, &outsider), None);
+        let borrowed = proof_for(&list, &list[0]).unwrap();
+        assert!(!verify(&borrowed, &root, leaf(&outsider)));
+    }
+
+    #[test]
+    fn rejects_proof_against_another_root() {
+        let list = wallets(4);
+        let other = wallets(4);
+        let proof = proof_for(&list, &list[2]).unwrap();
+        assert!(!verify(&proof, &root_for(&other).unwrap(), leaf(&list[2])));
+    }
+
+    #[test]
+    fn empty_list_has_no_root() {
+        assert_eq!(root_for(&[]), None);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::fees::BPS_DENOMINATOR;
+use crate::state::PriceCurve;
+
+// Fixed-point scale used while compounding exponential growth
+const SCALE: u128 = 1_000_000_000;
+
+// Price of the pass minted after `supply` passes have already been sold
+pub fn price_at(base_price: u64, curve: &PriceCurve, supply: u64) -> Option<u64> {
+    match curve {
+        PriceCurve::Flat => Some(base_price),
+        PriceCurve::Linear { increment } => base_price.checked_add(increment.checked_mul(supply)?),
+        PriceCurve::Exponential { growth_bps } => {
+            let growth = SCALE
+                .checked_add(SCALE.checked_mul(*growth_bps as u128)? / BPS_DENOMINATOR as u128)?;
+            let factor = scaled_pow(growth, supply)?;
+            let price = (base_price as u128).checked_mul(factor)? / SCALE;
+            u64::try_from(price).ok()
+        }
+    }
+}
+
+// Exponentiation by squaring on fixed-point values, rounding down at each step
+fn scaled_pow(mut base: u128, mut exp: u64) -> Option<u128> {
+    let mut result = SCALE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(base)? / SCALE;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = base.checked_mul(base)? / SCALE;
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_curve_ignores_supply() {
+        assert_eq!(price_at(500, &PriceCurve::Flat, 0), Some(500));
+        assert_eq!(price_at(500, &PriceCurve::Flat, u64::MAX), Some(500));
+    }
+
+    #[test]
+    fn linear_curve_adds_increment_per_pass() {
+        let curve = PriceCurve::Linear { increment: 10 };
+        assert_eq!(price_at(1_000, &curve, 0), Some(1_000));
+        assert_eq!(price_at(1_000, &curve, 1), Some(1_010));
+        assert_eq!(price_at(1_000, &curve, 250), Some(3_500));
+    }
+
+    #[test]
+    fn exponential_curve_compounds_growth() {
+        let curve = PriceCurve::Exponential { growth_bps: 1_000 };
+        assert_eq!(price_at(1_000_000, &curve, 0), Some(1_000_000));
+        assert_eq!(price_at(1_000_000, &curve, 1), Some(1_100_000));
+        assert_eq!(price_at(1_000_000, &curve, 2), Some(1_210_000));
+        assert_eq!(price_at(1_000_000, &curve, 10), Some(2_593_742));
+    }
+
+    #[test]
+    fn exponential_curve_doubles_at_full_growth() {
+        let curve = PriceCurve::Exponential {
+            growth_bps: BPS_DENOMINATOR,
+        };
+        assert_eq!(price_at(1, &curve, 20), Some(1 << 20));
+    }
+
+    #[test]
+    fn prices_are_non_decreasing() {
+        let curves = [
+            PriceCurve::Linear { increment: 7 },
+            PriceCurve::Exponential { growth_bps: 37 },
+        ];
+        for curve in curves.iter() {
+            let mut last = 0;
+            for supply in 0..500 {
+                let price = price_at(1_000_000_000, curve, supply).unwrap();
+                assert!(price >= last);
+                last = price;
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_returns_none() {
+        let linear = PriceCurve::Linear {
+            increment: u64::MAX,
+        };
+        assert_eq!(price_at(1, &linear, 2), None);
+
+        let exponential = PriceCurve::Exponential {
+            growth_bps: BPS_DENOMINATOR,
+        };
+        assert_eq!(price_at(1, &exponential, 64), None);
+        assert_eq!(price_at(u64::MAX, &exponential, 1), None);
+    }
+}
@@ -23,6 +23,18 @@ pub struct Claim {
     pub created_at: i64,
 
     pub bump: u8,
+
+    pub vote_extension_total: i64,
+
+    pub eligible_supply: u64,
+
+    pub crank_bounty: u64,
+
+    pub vote_extension_window: i64,
+
+    pub vote_extension_duration: i64,
+
+    pub max_vote_extension: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -34,8 +46,143 @@ pub enum ClaimStatus {
     Paid,
     Canceled,
     Refunded,
+    Expired,
 }
 
 impl Claim {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 4 + 64 + 1 + 8 + 8 + 8 + 8 + 8 + 1;
+    pub const LEN: usize =
+        8 + 32 + 32 + 8 + 4 + 64 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    // Once either side holds a majority of the pass supply snapshotted at filing,
+    // the remaining votes can no longer change the result
+    pub fn is_outcome_decided(&self) -> bool {
+        self.eligible_supply > 0
+            && (self.yes_votes.saturating_mul(2) > self.eligible_supply
+                || self.no_votes.saturating_mul(2) >= self.eligible_supply)
+    }
+
+    // Result of the vote once it can close: below quorum the claim expires, otherwise a
+    // strict yes majority approves it and anything else rejects it
+    pub fn voted_outcome(&self, quorum: u64) -> ClaimStatus {
+        let total_votes = self.yes_votes.saturating_add(self.no_votes);
+        if total_votes < quorum {
+            ClaimStatus::Expired
+        } else if self.yes_votes > self.no_votes {
+            ClaimStatus::Approved
+        } else {
+            ClaimStatus::Rejected
+        }
+    }
+
+    // Hands out the escrowed finalization bounty at most once
+    pub fn take_crank_bounty(&mut self) -> u64 {
+        std::mem::take(&mut self.crank_bounty)
+    }
+
+    // How far a vote at `now` that flipped the lead pushes back the end of voting, under
+    // the extension rule copied from the pool when the claim was filed
+    pub fn vote_extension(&self, was_passing: bool, now: i64) -> i64 {
+        if self.vote_extension_window == 0 || self.vote_extension_duration == 0 {
+            return 0;
+        }
+
+        let is_passing = self.yes_votes > self.no_votes;
+        let extension_starts_at = self
+            .voting_ends_at
+            .saturating_sub(self.vote_extension_window);
+        if is_passing == was_passing || now < extension_starts_at {
+            return 0;
+        }
+
+        let remaining = self
+            .max_vote_extension
+            .saturating_sub(self.vote_extension_total);
+        self.vote_extension_duration.min(remaining).max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(yes_votes: u64, no_votes: u64, eligible_supply: u64) -> Claim {
+        Claim {
+            creator_pool: Pubkey::default(),
+            creator: Pubkey::default(),
+            pool_amount_at_claim: 0,
+            evidence_ipfs_hash: String::new(),
+            status: ClaimStatus::Voting,
+            yes_votes,
+            no_votes,
+            voting_started_at: 0,
+            voting_ends_at: 1_000,
+            created_at: 0,
+            bump: 0,
+            vote_extension_total: 0,
+            eligible_supply,
+            crank_bounty: 0,
+            vote_extension_window: 100,
+            vote_extension_duration: 60,
+            max_vote_extension: 90,
+        }
+    }
+
+    #[test]
+    fn outcome_is_decided_by_a_majority_of_eligible_passes() {
+        assert!(!claim(5, 0, 10).is_outcome_decided());
+        assert!(claim(6, 0, 10).is_outcome_decided());
+        // A no-side half means yes can at best tie, which rejects
+        assert!(claim(0, 5, 10).is_outcome_decided());
+        assert!(!claim(0, 4, 10).is_outcome_decided());
+    }
+
+    #[test]
+    fn outcome_is_never_decided_without_a_snapshot() {
+        assert!(!claim(100, 0, 0).is_outcome_decided());
+    }
+
+    #[test]
+    fn voted_outcome_expires_below_quorum_and_rejects_ties() {
+        assert!(claim(2, 0, 10).voted_outcome(3) == ClaimStatus::Expired);
+        assert!(claim(2, 1, 10).voted_outcome(3) == ClaimStatus::Approved);
+        assert!(claim(2, 2, 10).voted_outcome(3) == ClaimStatus::Rejected);
+    }
+
+    #[test]
+    fn crank_bounty_is_paid_once() {
+        let mut claim = claim(0, 0, 0);
+        claim.crank_bounty = 5_000;
+        assert_eq!(claim.take_crank_bounty(), 5_000);
+        assert_eq!(claim.take_crank_bounty(), 0);
+    }
+
+    #[test]
+    fn flip_inside_the_window_extends_voting() {
+        // Yes took the lead from a losing position
+        let claim = claim(3, 2, 10);
+        assert_eq!(claim.vote_extension(false, 950), 60);
+    }
+
+    #[test]
+    fn votes_outside_the_window_or_without_a_flip_do_not_extend() {
+        let claim = claim(3, 2, 10);
+        assert_eq!(claim.vote_extension(false, 899), 0);
+        assert_eq!(claim.vote_extension(true, 950), 0);
+    }
+
+    #[test]
+    fn extensions_stop_at_the_cap() {
+        let mut claim = claim(3, 2, 10);
+        claim.vote_extension_total = 60;
+        assert_eq!(claim.vote_extension(false, 950), 30);
+        claim.vote_extension_total = 90;
+        assert_eq!(claim.vote_extension(false, 950), 0);
+    }
+
+    #[test]
+    fn disabled_extension_rule_never_extends() {
+        let mut claim = claim(3, 2, 10);
+        claim.vote_extension_duration = 0;
+        assert_eq!(claim.vote_extension(false, 950), 0);
+    }
 }
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ClaimBond {
+    pub claim: Pubkey,
+
+    pub creator: Pubkey,
+
+    pub amount: u64,
+
+    pub bump: u8,
+}
+
+impl ClaimBond {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
@@ -1,3 +1,4 @@
+use crate::pricing::price_at;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -15,8 +16,108 @@ pub struct CreatorCollection {
     pub created_at: i64,
 
     pub bump: u8,
+
+    pub base_price: u64,
+
+    pub price_curve: PriceCurve,
+
+    pub max_supply: u64,
+
+    pub max_per_wallet: u64,
+
+    pub presale_merkle_root: [u8; 32],
+
+    pub presale_price: u64,
+
+    pub presale_starts_at: i64,
+
+    pub presale_ends_at: i64,
+
+    pub referral_bps: u64,
+
+    pub royalty_bps: u64,
+
+    pub merkle_tree: Pubkey,
+
+    pub core_collection: Pubkey,
+
+    pub soulbound: bool,
+
+    pub exit_fee_bps: u64,
+
+    pub pass_duration: i64,
+
+    pub renewal_price: u64,
+
+    pub last_revoked_at: i64,
+
+    pub registered_passes: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum PriceCurve {
+    Flat,
+    Linear { increment: u64 },
+    Exponential { growth_bps: u64 },
+}
+
+impl PriceCurve {
+    pub const LEN: usize = 1 + 8;
 }
 
 impl CreatorCollection {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + 8
+        + PriceCurve::LEN
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 32
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8;
+
+    // Minted passes plus the compressed and Core passes registered for voting
+    pub fn pass_supply(&self) -> Option<u64> {
+        self.total_supply.checked_add(self.registered_passes)
+    }
+
+    // A zero cap means the collection has no supply limit
+    pub fn is_sold_out(&self) -> bool {
+        self.max_supply > 0
+            && self.total_supply.saturating_add(self.registered_passes) >= self.max_supply
+    }
+
+    // Until an allowlist presale ends, only allowlisted wallets may buy
+    pub fn in_presale(&self, now: i64) -> bool {
+        self.presale_merkle_root != [0; 32] && now < self.presale_ends_at
+    }
+
+    // Passes from collections without a duration never expire
+    pub fn valid_until(&self, from: i64) -> Option<i64> {
+        if self.pass_duration == 0 {
+            return Some(0);
+        }
+        from.checked_add(self.pass_duration)
+    }
+
+    pub fn next_price(&self) -> Option<u64> {
+        price_at(self.base_price, &self.price_curve, self.total_supply)
+    }
 }
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct CreatorFeeOverride {
+    pub creator: Pubkey,
+
+    pub platform_fee_bps: u64,
+
+    pub expires_at: i64,
+
+    pub bump: u8,
+}
+
+impl CreatorFeeOverride {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+
+    // Reads the override from its PDA, which is uninitialized for creators without one
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.owner != &crate::ID || account.data_is_empty() {
+            return Ok(None);
+        }
+        let data = account.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    // An expiry of zero means the override never lapses
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
@@ -21,6 +21,30 @@ pub struct CreatorPool {
     pub claim_count: u64,
 
     pub bump: u8,
+
+    pub vote_extension_window: i64,
+
+    pub vote_extension_duration: i64,
+
+    pub max_vote_extension: i64,
+
+    pub misconduct_proposal_count: u64,
+
+    pub refund_snapshot_lamports: u64,
+
+    pub refund_snapshot_supply: u64,
+
+    pub last_heartbeat: i64,
+
+    pub vault_share_bps: u64,
+
+    pub pending_vault_share_bps: u64,
+
+    pub vault_share_effective_at: i64,
+
+    pub active_claims: u64,
+
+    pub active_proposals: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -28,8 +52,31 @@ pub enum PoolStatus {
     Active,
     Paused,
     Closed,
+    Refunding,
 }
 
 impl CreatorPool {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 1;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8;
 }
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::fees::BPS_DENOMINATOR;
+use crate::state::CreatorFeeOverride;
+
 #[account]
 pub struct Factory {
     pub authority: Pubkey,
@@ -8,13 +11,53 @@ pub struct Factory {
 
     pub default_voting_window: i64,
 
-    pub platform_fee_percentage: u64,
+    pub platform_fee_bps: u64,
 
     pub usdc_mint: Pubkey,
 
     pub bump: u8,
+
+    pub crank_bounty: u64,
+
+    pub filing_bond_amount: u64,
+
+    pub filing_bond_bps: u64,
+
+    pub misconduct_cosign_threshold: u64,
+
+    pub inactivity_period: i64,
+
+    pub max_platform_fee_bps: u64,
+
+    pub default_vault_share_bps: u64,
+
+    pub min_vault_share_bps: u64,
+
+    pub max_vault_share_bps: u64,
+
+    pub vault_share_timelock: i64,
+
+    pub revocation_cooldown: i64,
 }
 
 impl Factory {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    // Fans always get at least a day's notice before a vault share cut
+    pub const MIN_VAULT_SHARE_TIMELOCK: i64 = 24 * 60 * 60;
+
+    pub fn platform_fee_bps_for(&self, fee_override: Option<&CreatorFeeOverride>, now: i64) -> u64 {
+        match fee_override {
+            Some(fee_override) if fee_override.is_active(now) => fee_override.platform_fee_bps,
+            _ => self.platform_fee_bps,
+        }
+    }
+
+    // The share must leave room for the highest platform fee a creator can be charged,
+    // otherwise split_sale fails and the pool's sales stop
+    pub fn is_valid_vault_share(&self, vault_share_bps: u64) -> bool {
+        vault_share_bps >= self.min_vault_share_bps
+            && vault_share_bps <= self.max_vault_share_bps
+            && vault_share_bps.saturating_add(self.max_platform_fee_bps) <= BPS_DENOMINATOR
+    }
 }
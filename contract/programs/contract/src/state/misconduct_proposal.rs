@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VoteChoice;
+
+#[account]
+pub struct MisconductProposal {
+    pub creator_pool: Pubkey,
+
+    pub proposer: Pubkey,
+
+    pub evidence_ipfs_hash: String,
+
+    pub status: ProposalStatus,
+
+    pub cosign_count: u64,
+
+    pub yes_votes: u64,
+
+    pub no_votes: u64,
+
+    pub voting_ends_at: i64,
+
+    pub created_at: i64,
+
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Gathering,
+    Voting,
+    Passed,
+    Failed,
+}
+
+impl MisconductProposal {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 64 + 1 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct ProposalCosign {
+    pub proposal: Pubkey,
+
+    pub cosigner: Pubkey,
+
+    pub nft_mint: Pubkey,
+
+    pub bump: u8,
+}
+
+impl ProposalCosign {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1;
+}
+
+#[account]
+pub struct ProposalVote {
+    pub proposal: Pubkey,
+
+    pub voter: Pubkey,
+
+    pub nft_mint: Pubkey,
+
+    pub vote_choice: VoteChoice,
+
+    pub voted_at: i64,
+
+    pub bump: u8,
+}
+
+impl ProposalVote {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 8 + 1;
+}
@@ -1,15 +1,35 @@
 pub mod claim;
+pub mod claim_bond;
 pub mod creator_collection;
+pub mod creator_fee_override;
 pub mod creator_pool;
 pub mod factory;
+pub mod misconduct_proposal;
 pub mod nft_ownership;
+pub mod pass_listing;
+pub mod pass_purchase_counter;
+pub mod pool_refund_receipt;
+pub mod referrer_stats;
 pub mod refund_distribution;
+pub mod registered_pass;
+pub mod transfer_approval;
+pub mod treasury;
 pub mod vote_account;
 
 pub use claim::*;
+pub use claim_bond::*;
 pub use creator_collection::*;
+pub use creator_fee_override::*;
 pub use creator_pool::*;
 pub use factory::*;
+pub use misconduct_proposal::*;
 pub use nft_ownership::*;
+pub use pass_listing::*;
+pub use pass_purchase_counter::*;
+pub use pool_refund_receipt::*;
+pub use referrer_stats::*;
 pub use refund_distribution::*;
+pub use registered_pass::*;
+pub use transfer_approval::*;
+pub use treasury::*;
 pub use vote_account::*;
@@ -13,8 +13,27 @@ pub struct NftOwnership {
     pub registered_at: i64,
 
     pub bump: u8,
+
+    pub acquired_at: i64,
+
+    pub tier: u8,
+
+    pub valid_until: i64,
+
+    pub vault_contribution: u64,
+
+    pub revoked: bool,
 }
 
 impl NftOwnership {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1 + 8 + 1 + 8 + 8 + 1;
+
+    // Passes bought from the public sale vs. the allowlist presale
+    pub const TIER_PUBLIC: u8 = 0;
+    pub const TIER_PRESALE: u8 = 1;
+
+    // A zero valid_until means the pass never expires
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.valid_until > 0 && now >= self.valid_until
+    }
 }
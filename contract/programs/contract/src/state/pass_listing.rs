@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PassListing {
+    pub seller: Pubkey,
+
+    pub nft_mint: Pubkey,
+
+    pub creator_collection: Pubkey,
+
+    pub price: u64,
+
+    pub listed_at: i64,
+
+    pub bump: u8,
+}
+
+impl PassListing {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PassPurchaseCounter {
+    pub creator_collection: Pubkey,
+
+    pub buyer: Pubkey,
+
+    pub purchased: u64,
+
+    pub presale_claimed: bool,
+
+    pub bump: u8,
+}
+
+impl PassPurchaseCounter {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1 + 1;
+}
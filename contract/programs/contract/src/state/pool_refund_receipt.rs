@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PoolRefundReceipt {
+    pub creator_pool: Pubkey,
+
+    pub holder: Pubkey,
+
+    pub nft_mint: Pubkey,
+
+    pub amount: u64,
+
+    pub withdrawn_at: i64,
+
+    pub bump: u8,
+}
+
+impl PoolRefundReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct ReferrerStats {
+    pub referrer: Pubkey,
+
+    pub referral_count: u64,
+
+    pub total_earned: u64,
+
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+}
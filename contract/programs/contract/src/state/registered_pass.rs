@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// Holder record for a compressed or Core pass, which has no mint to hang an nft_ownership on
+#[account]
+pub struct RegisteredPass {
+    pub creator_collection: Pubkey,
+
+    pub asset: Pubkey,
+
+    pub owner: Pubkey,
+
+    pub acquired_at: i64,
+
+    pub bump: u8,
+}
+
+impl RegisteredPass {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct TransferApproval {
+    pub nft_mint: Pubkey,
+
+    pub owner: Pubkey,
+
+    pub recipient: Pubkey,
+
+    pub royalty: u64,
+
+    pub approved_at: i64,
+
+    pub bump: u8,
+}
+
+impl TransferApproval {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
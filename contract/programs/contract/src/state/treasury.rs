@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Treasury {
+    pub factory: Pubkey,
+
+    pub total_collected: u64,
+
+    pub total_withdrawn: u64,
+
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use contract::cpi::accounts::VerifyFanPass;
+use contract::program::Contract;
+
+declare_id!("EwEim1RLfTSMGyZYgt1j2bDNW5AHdrkQHCN5uUV529sx");
+
+// Minimal integrator showing how another program gates an action on a creator's fan pass
+#[program]
+pub mod pass_gate {
+    use super::*;
+
+    pub fn enter_room(ctx: Context<EnterRoom>, creator: Pubkey, min_tier: u8) -> Result<()> {
+        let pass = contract::cpi::verify_fan_pass(CpiContext::new(
+            ctx.accounts.contract_program.to_account_info(),
+            VerifyFanPass {
+                fan: ctx.accounts.fan.to_account_info(),
+                nft_mint: ctx.accounts.nft_mint.to_account_info(),
+                fan_token_account: ctx.accounts.fan_token_account.to_account_info(),
+                creator_collection: ctx.accounts.creator_collection.to_account_info(),
+                nft_ownership: ctx.accounts.nft_ownership.to_account_info(),
+                nft_metadata: ctx
+                    .accounts
+                    .nft_metadata
+                    .as_ref()
+                    .map(|metadata| metadata.to_account_info()),
+            },
+        ))?
+        .get();
+
+        require_keys_eq!(pass.creator, creator, ErrorCode::WrongCreator);
+        require!(pass.tier >= min_tier, ErrorCode::TierTooLow);
+
+        emit!(RoomEntered {
+            holder: pass.holder,
+            creator: pass.creator,
+            tier: pass.tier,
+            acquired_at: pass.acquired_at,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct EnterRoom<'info> {
+    pub fan: Signer<'info>,
+
+    /// CHECK: Validated by the contract program
+    pub nft_mint: AccountInfo<'info>,
+
+    /// CHECK: Validated by the contract program
+    pub fan_token_account: AccountInfo<'info>,
+
+    /// CHECK: Validated by the contract program
+    pub creator_collection: AccountInfo<'info>,
+
+    /// CHECK: Validated by the contract program
+    pub nft_ownership: AccountInfo<'info>,
+
+    /// CHECK: Validated by the contract program
+    pub nft_metadata: Option<AccountInfo<'info>>,
+
+    pub contract_program: Program<'info, Contract>,
+}
+
+#[event]
+pub struct RoomEntered {
+    pub holder: Pubkey,
+    pub creator: Pubkey,
+    pub tier: u8,
+    pub acquired_at: i64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Pass was issued by a different creator")]
+    WrongCreator,
+    #[msg("Pass tier is below the room's minimum")]
+    TierTooLow,
+}
@@ -0,0 +1,223 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  createTree,
+  hashLeaf,
+  hashMetadataCreators,
+  hashMetadataData,
+  MetadataArgsArgs,
+  mintV1,
+  mplBubblegum,
+} from "@metaplex-foundation/mpl-bubblegum";
+import { generateSigner, keypairIdentity, none } from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  fromWeb3JsKeypair,
+  fromWeb3JsPublicKey,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+import { MerkleTree } from "@solana/spl-account-compression";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  connection,
+  createCollection,
+  fileClaim,
+  pda,
+  program,
+  sleep,
+} from "./setup";
+
+const BUBBLEGUM_PROGRAM_ID = new PublicKey(
+  "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfxf1JFw8iyQ"
+);
+const ACCOUNT_COMPRESSION_PROGRAM_ID = new PublicKey(
+  "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK"
+);
+const MAX_DEPTH = 3;
+
+describe("compressed passes", () => {
+  const creator = Keypair.generate();
+  const fan = Keypair.generate();
+  const lateFan = Keypair.generate();
+  const umi = createUmi(connection.rpcEndpoint, "confirmed").use(
+    mplBubblegum()
+  );
+  const metadata: MetadataArgsArgs = {
+    name: "Fan pass",
+    symbol: "",
+    uri: "https://example.com/pass.json",
+    sellerFeeBasisPoints: 0,
+    collection: none(),
+    creators: [],
+  };
+  const leaves: Buffer[] = [];
+  let merkleTree: PublicKey;
+  let creatorCollection: PublicKey;
+  let creatorPool: PublicKey;
+
+  // Mints the next leaf to `owner` and tracks its hash for later proofs
+  const mintPass = async (owner: PublicKey) => {
+    const nonce = leaves.length;
+    await mintV1(umi, {
+      leafOwner: fromWeb3JsPublicKey(owner),
+      merkleTree: fromWeb3JsPublicKey(merkleTree),
+      metadata,
+    }).sendAndConfirm(umi);
+    leaves.push(
+      Buffer.from(
+        hashLeaf(umi, {
+          merkleTree: fromWeb3JsPublicKey(merkleTree),
+          owner: fromWeb3JsPublicKey(owner),
+          leafIndex: nonce,
+          metadata,
+        })
+      )
+    );
+
+    return { owner, nonce };
+  };
+
+  // Rebuilds the tree from the minted leaves so the proof matches the current root
+  const proofFor = ({ owner, nonce }: { owner: PublicKey; nonce: number }) => {
+    const tree = MerkleTree.sparseMerkleTreeFromLeaves(leaves, MAX_DEPTH);
+    const { root, proof } = tree.getProof(nonce);
+
+    return {
+      args: {
+        root: Array.from(root),
+        dataHash: Array.from(hashMetadataData(metadata)),
+        creatorHash: Array.from(hashMetadataCreators([])),
+        delegate: owner,
+        nonce: new anchor.BN(nonce),
+        index: nonce,
+      },
+      nodes: proof.map((node) => ({
+        pubkey: new PublicKey(node),
+        isSigner: false,
+        isWritable: false,
+      })),
+      assetId: PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("asset"),
+          merkleTree.toBuffer(),
+          new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+        ],
+        BUBBLEGUM_PROGRAM_ID
+      )[0],
+    };
+  };
+
+  const registerPass = (
+    pass: { owner: PublicKey; nonce: number },
+    holder: Keypair
+  ) => {
+    const { args, nodes, assetId } = proofFor(pass);
+    return program.methods
+      .registerCompressedPass(args)
+      .accountsPartial({
+        creatorCollection,
+        merkleTree,
+        compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        registeredPass: pda("registered_pass", assetId),
+        fan: holder.publicKey,
+      })
+      .remainingAccounts(nodes)
+      .signers([holder])
+      .rpc();
+  };
+
+  const voteWithPass = (
+    claim: PublicKey,
+    pass: { owner: PublicKey; nonce: number },
+    holder: Keypair
+  ) => {
+    const { args, nodes, assetId } = proofFor(pass);
+    return program.methods
+      .voteWithCompressedPass({ yes: {} }, args)
+      .accountsPartial({
+        claim,
+        creatorPool,
+        creatorCollection,
+        merkleTree,
+        compressionProgram: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        registeredPass: pda("registered_pass", assetId),
+        voteAccount: pda("vote", claim, assetId),
+        fan: holder.publicKey,
+      })
+      .remainingAccounts(nodes)
+      .signers([holder])
+      .rpc();
+  };
+
+  before(async () => {
+    await airdrop(fan.publicKey, 2);
+    await airdrop(lateFan.publicKey, 2);
+    ({ creatorCollection, creatorPool } = await createCollection(creator, {
+      maxSupply: 10,
+    }));
+
+    umi.use(keypairIdentity(fromWeb3JsKeypair(creator)));
+    const tree = generateSigner(umi);
+    await (
+      await createTree(umi, {
+        merkleTree: tree,
+        maxDepth: MAX_DEPTH,
+        maxBufferSize: 8,
+        public: false,
+      })
+    ).sendAndConfirm(umi);
+    merkleTree = toWeb3JsPublicKey(tree.publicKey);
+
+    await program.methods
+      .setCompressedTree()
+      .accountsPartial({
+        creatorCollection,
+        merkleTree,
+        treeConfig: PublicKey.findProgramAddressSync(
+          [merkleTree.toBuffer()],
+          BUBBLEGUM_PROGRAM_ID
+        )[0],
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("counts registered passes in the claim supply and lets them vote", async () => {
+    const pass = await mintPass(fan.publicKey);
+    await registerPass(pass, fan);
+
+    const registered = await program.account.registeredPass.fetch(
+      pda("registered_pass", proofFor(pass).assetId)
+    );
+    assert.ok(registered.owner.equals(fan.publicKey));
+    const collection = await program.account.creatorCollection.fetch(
+      creatorCollection
+    );
+    assert.equal(collection.registeredPasses.toNumber(), 1);
+
+    const claim = await fileClaim(creator);
+    await voteWithPass(claim, pass, fan);
+
+    const filed = await program.account.claim.fetch(claim);
+    assert.equal(filed.eligibleSupply.toNumber(), 1);
+    assert.equal(filed.yesVotes.toNumber(), 1);
+  });
+
+  it("rejects a compressed pass registered after the claim was filed", async () => {
+    const claim = await fileClaim(creator);
+
+    // Let the clock move past the filing time before the late pass is registered
+    await sleep(1_500);
+    const pass = await mintPass(lateFan.publicKey);
+    await registerPass(pass, lateFan);
+
+    try {
+      await voteWithPass(claim, pass, lateFan);
+      assert.fail("late pass should not vote");
+    } catch (err) {
+      assert.include(String(err), "PassAcquiredAfterClaim");
+    }
+  });
+});
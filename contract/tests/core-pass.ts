@@ -0,0 +1,139 @@
+import {
+  create,
+  createCollection as createCoreCollection,
+  mplCore,
+} from "@metaplex-foundation/mpl-core";
+import { generateSigner, keypairIdentity } from "@metaplex-foundation/umi";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  fromWeb3JsKeypair,
+  fromWeb3JsPublicKey,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  connection,
+  createCollection,
+  fileClaim,
+  pda,
+  program,
+  sleep,
+} from "./setup";
+
+describe("core passes", () => {
+  const creator = Keypair.generate();
+  const fan = Keypair.generate();
+  const lateFan = Keypair.generate();
+  const umi = createUmi(connection.rpcEndpoint, "confirmed").use(mplCore());
+  let coreCollection: PublicKey;
+  let creatorCollection: PublicKey;
+  let creatorPool: PublicKey;
+
+  // Creates an asset in the creator's Core collection owned by `owner`
+  const mintPass = async (owner: PublicKey) => {
+    const asset = generateSigner(umi);
+    await create(umi, {
+      asset,
+      collection: {
+        publicKey: fromWeb3JsPublicKey(coreCollection),
+        oracles: [],
+        lifecycleHooks: [],
+      },
+      owner: fromWeb3JsPublicKey(owner),
+      name: "Fan pass",
+      uri: "https://example.com/pass.json",
+    }).sendAndConfirm(umi);
+
+    return toWeb3JsPublicKey(asset.publicKey);
+  };
+
+  const registerPass = (asset: PublicKey, holder: Keypair) =>
+    program.methods
+      .registerCorePass()
+      .accountsPartial({
+        creatorCollection,
+        asset,
+        registeredPass: pda("registered_pass", asset),
+        fan: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc();
+
+  const voteWithPass = (claim: PublicKey, asset: PublicKey, holder: Keypair) =>
+    program.methods
+      .voteWithCorePass({ yes: {} })
+      .accountsPartial({
+        claim,
+        creatorPool,
+        creatorCollection,
+        asset,
+        registeredPass: pda("registered_pass", asset),
+        voteAccount: pda("vote", claim, asset),
+        fan: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc();
+
+  before(async () => {
+    await airdrop(fan.publicKey, 2);
+    await airdrop(lateFan.publicKey, 2);
+    ({ creatorCollection, creatorPool } = await createCollection(creator, {
+      maxSupply: 10,
+    }));
+
+    // The creator is the Core collection's update authority
+    umi.use(keypairIdentity(fromWeb3JsKeypair(creator)));
+    const collection = generateSigner(umi);
+    await createCoreCollection(umi, {
+      collection,
+      name: "Fan passes",
+      uri: "https://example.com/collection.json",
+    }).sendAndConfirm(umi);
+    coreCollection = toWeb3JsPublicKey(collection.publicKey);
+
+    await program.methods
+      .setCoreCollection()
+      .accountsPartial({
+        creatorCollection,
+        coreCollection,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("counts registered Core passes in the claim supply and lets them vote", async () => {
+    const asset = await mintPass(fan.publicKey);
+    await registerPass(asset, fan);
+
+    const registered = await program.account.registeredPass.fetch(
+      pda("registered_pass", asset)
+    );
+    assert.ok(registered.owner.equals(fan.publicKey));
+
+    const claim = await fileClaim(creator);
+    await voteWithPass(claim, asset, fan);
+
+    const filed = await program.account.claim.fetch(claim);
+    assert.equal(filed.eligibleSupply.toNumber(), 1);
+    assert.equal(filed.yesVotes.toNumber(), 1);
+  });
+
+  it("rejects a Core pass registered after the claim was filed", async () => {
+    const claim = await fileClaim(creator);
+
+    // Let the clock move past the filing time before the late pass is registered
+    await sleep(1_500);
+    const asset = await mintPass(lateFan.publicKey);
+    await registerPass(asset, lateFan);
+
+    try {
+      await voteWithPass(claim, asset, lateFan);
+      assert.fail("late pass should not vote");
+    } catch (err) {
+      assert.include(String(err), "PassAcquiredAfterClaim");
+    }
+  });
+});
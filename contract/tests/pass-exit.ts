@@ -0,0 +1,113 @@
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  balance,
+  bpsOf,
+  buyPass,
+  createCollection,
+  passAccount,
+  pda,
+  program,
+  unreservedVault,
+} from "./setup";
+
+describe("pass exits", () => {
+  it("redeems a pass for its pro-rata vault share less the exit fee", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    const otherFan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    await airdrop(otherFan.publicKey, 5);
+    const exitFeeBps = 1_000;
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator, { exitFeeBps });
+    const passMint = await buyPass(creator.publicKey, fan);
+    await buyPass(creator.publicKey, otherFan);
+
+    const nftOwnership = pda("nft_ownership", passMint);
+    const holderTokenAccount = passAccount(passMint, fan.publicKey);
+    const share = Math.floor((await unreservedVault(solVault)) / 2);
+    const exitFee = bpsOf(share, exitFeeBps);
+    const closedRent =
+      (await balance(nftOwnership)) + (await balance(holderTokenAccount));
+
+    const accounts = [solVault, fan.publicKey];
+    const before = await Promise.all(accounts.map(balance));
+    await program.methods
+      .redeemPass()
+      .accountsPartial({
+        creatorCollection,
+        creatorPool,
+        solVault,
+        nftOwnership,
+        passMint,
+        holderTokenAccount,
+        holder: fan.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([fan])
+      .rpc();
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(before[0] - after[0], share - exitFee);
+    assert.equal(after[1] - before[1], share - exitFee + closedRent);
+
+    const collection = await program.account.creatorCollection.fetch(
+      creatorCollection
+    );
+    assert.equal(collection.totalSupply.toNumber(), 1);
+  });
+
+  it("refunds a revoked pass without lowering the other holders' share", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    const otherFan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    await airdrop(otherFan.publicKey, 5);
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator);
+
+    // The purchase's vault share is recorded as the pass's contribution
+    const vaultBefore = await balance(solVault);
+    const passMint = await buyPass(creator.publicKey, fan);
+    const vaultAmount = (await balance(solVault)) - vaultBefore;
+    await buyPass(creator.publicKey, otherFan);
+
+    const nftOwnership = pda("nft_ownership", passMint);
+    const ownership = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.equal(ownership.vaultContribution.toNumber(), vaultAmount);
+
+    // The pool's opening deposit puts each pass's share above its contribution
+    const unreserved = await unreservedVault(solVault);
+    const share = Math.floor(unreserved / 2);
+    const refund = Math.min(
+      Math.max(share, vaultAmount),
+      unreserved - share
+    );
+
+    const accounts = [solVault, fan.publicKey];
+    const before = await Promise.all(accounts.map(balance));
+    await program.methods
+      .revokePass(Array(32).fill(0))
+      .accountsPartial({
+        creatorCollection,
+        creatorPool,
+        solVault,
+        nftOwnership,
+        holder: fan.publicKey,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(before[0] - after[0], refund);
+    assert.equal(after[1] - before[1], refund);
+    assert.isAtLeast(await unreservedVault(solVault), share);
+
+    const revoked = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.isTrue(revoked.revoked);
+  });
+});
@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import { PassGate } from "../target/types/pass_gate";
+import {
+  airdrop,
+  buyPass,
+  connection,
+  createCollection,
+  passAccount,
+  pda,
+  program,
+} from "./setup";
+
+describe("pass gate", () => {
+  const passGate = anchor.workspace.passGate as Program<PassGate>;
+
+  const enterRoom = (
+    creator: PublicKey,
+    creatorCollection: PublicKey,
+    passMint: PublicKey,
+    fan: Keypair
+  ) =>
+    passGate.methods
+      .enterRoom(creator, 0)
+      .accountsPartial({
+        fan: fan.publicKey,
+        nftMint: passMint,
+        fanTokenAccount: passAccount(passMint, fan.publicKey),
+        creatorCollection,
+        nftOwnership: pda("nft_ownership", passMint),
+        nftMetadata: null,
+        contractProgram: program.programId,
+      })
+      .signers([fan])
+      .rpc({ commitment: "confirmed" });
+
+  it("lets a pass holder into the room through the verify_fan_pass CPI", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    const { creatorCollection } = await createCollection(creator);
+    const passMint = await buyPass(creator.publicKey, fan);
+
+    const signature = await enterRoom(
+      creator.publicKey,
+      creatorCollection,
+      passMint,
+      fan
+    );
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const [entered] = [
+      ...new anchor.EventParser(passGate.programId, passGate.coder).parseLogs(
+        tx.meta.logMessages
+      ),
+    ];
+
+    assert.equal(entered.name, "roomEntered");
+    assert.ok(entered.data.holder.equals(fan.publicKey));
+    assert.ok(entered.data.creator.equals(creator.publicKey));
+  });
+
+  it("turns away a revoked pass", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator);
+    const passMint = await buyPass(creator.publicKey, fan);
+
+    await program.methods
+      .revokePass(Array(32).fill(0))
+      .accountsPartial({
+        creatorCollection,
+        creatorPool,
+        solVault,
+        nftOwnership: pda("nft_ownership", passMint),
+        holder: fan.publicKey,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    try {
+      await enterRoom(creator.publicKey, creatorCollection, passMint, fan);
+      assert.fail("revoked pass entered the room");
+    } catch (err) {
+      assert.include(String(err), "PassRevoked");
+    }
+  });
+});
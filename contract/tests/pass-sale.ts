@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  balance,
+  bpsOf,
+  buyPass,
+  createCollection,
+  passAccount,
+  pda,
+  PASS_PRICE,
+  program,
+} from "./setup";
+
+describe("pass sales", () => {
+  // Platform, creator and vault amounts of a primary sale at PASS_PRICE
+  const quotePrimarySale = async (creatorPool: PublicKey) => {
+    const factory = await program.account.factory.fetch(pda("factory"));
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    const price = PASS_PRICE.toNumber();
+    const platformFee = bpsOf(price, factory.platformFeeBps.toNumber());
+    const creatorAmount = bpsOf(
+      price,
+      10_000 - factory.platformFeeBps.toNumber() - pool.vaultShareBps.toNumber()
+    );
+
+    return {
+      platformFee,
+      creatorAmount,
+      vaultAmount: price - platformFee - creatorAmount,
+    };
+  };
+
+  it("splits a fan-paid purchase between vault, creator and treasury", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    const { creatorPool, solVault } = await createCollection(creator);
+    const sale = await quotePrimarySale(creatorPool);
+
+    const accounts = [solVault, creator.publicKey, pda("treasury")];
+    const before = await Promise.all(accounts.map(balance));
+    await buyPass(creator.publicKey, fan);
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(after[0] - before[0], sale.vaultAmount);
+    assert.equal(after[1] - before[1], sale.creatorAmount);
+    assert.equal(after[2] - before[2], sale.platformFee);
+  });
+
+  it("pays the referral reward out of the creator's share", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    const referrer = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    await airdrop(referrer.publicKey, 1);
+    const referralBps = 1_000;
+    const { creatorPool, solVault } = await createCollection(creator, {
+      referralBps,
+    });
+    await program.methods
+      .registerReferrer()
+      .accounts({ referrer: referrer.publicKey })
+      .signers([referrer])
+      .rpc();
+    const sale = await quotePrimarySale(creatorPool);
+    const reward = bpsOf(sale.creatorAmount, referralBps);
+
+    const accounts = [solVault, creator.publicKey, referrer.publicKey];
+    const before = await Promise.all(accounts.map(balance));
+    await buyPass(creator.publicKey, fan, referrer.publicKey);
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(after[0] - before[0], sale.vaultAmount);
+    assert.equal(after[1] - before[1], sale.creatorAmount - reward);
+    assert.equal(after[2] - before[2], reward);
+
+    const stats = await program.account.referrerStats.fetch(
+      pda("referrer_stats", referrer.publicKey)
+    );
+    assert.equal(stats.referralCount.toNumber(), 1);
+    assert.equal(stats.totalEarned.toNumber(), reward);
+  });
+
+  it("splits a resale royalty between vault and creator and pays the seller the rest", async () => {
+    const creator = Keypair.generate();
+    const seller = Keypair.generate();
+    const buyer = Keypair.generate();
+    await airdrop(seller.publicKey, 5);
+    await airdrop(buyer.publicKey, 5);
+    const royaltyBps = 1_000;
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator, { royaltyBps });
+    const passMint = await buyPass(creator.publicKey, seller);
+    const nftOwnership = pda("nft_ownership", passMint);
+
+    const listing = pda("listing", passMint);
+    const escrow = passAccount(passMint, listing);
+    const price = 2 * LAMPORTS_PER_SOL;
+    await program.methods
+      .listPass(new anchor.BN(price))
+      .accountsPartial({
+        nftOwnership,
+        creatorCollection,
+        passMint,
+        seller: seller.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([seller])
+      .rpc();
+
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    const royalty = bpsOf(price, royaltyBps);
+    const creatorAmount = bpsOf(royalty, 10_000 - pool.vaultShareBps.toNumber());
+    const closedRent = (await balance(listing)) + (await balance(escrow));
+
+    const accounts = [solVault, creator.publicKey, seller.publicKey];
+    const before = await Promise.all(accounts.map(balance));
+    await program.methods
+      .buyListedPass(new anchor.BN(price))
+      .accountsPartial({
+        listing,
+        nftOwnership,
+        creatorCollection,
+        creatorPool,
+        solVault,
+        creatorWallet: creator.publicKey,
+        passMint,
+        escrowTokenAccount: escrow,
+        buyerTokenAccount: passAccount(passMint, buyer.publicKey),
+        seller: seller.publicKey,
+        buyer: buyer.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(after[0] - before[0], royalty - creatorAmount);
+    assert.equal(after[1] - before[1], creatorAmount);
+    assert.equal(after[2] - before[2], price - royalty + closedRent);
+
+    const ownership = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.ok(ownership.owner.equals(buyer.publicKey));
+  });
+});
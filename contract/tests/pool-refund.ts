@@ -0,0 +1,177 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  balance,
+  buyPass,
+  createCollection,
+  pda,
+  program,
+  provider,
+  sleep,
+  unreservedVault,
+} from "./setup";
+
+describe("pool refunds", () => {
+  const setInactivityPeriod = (seconds: number) =>
+    program.methods
+      .updateFactory({
+        platformFeeBps: null,
+        crankBounty: null,
+        filingBondAmount: null,
+        filingBondBps: null,
+        misconductCosignThreshold: null,
+        inactivityPeriod: new anchor.BN(seconds),
+        defaultVaultShareBps: null,
+        minVaultShareBps: null,
+        maxVaultShareBps: null,
+        vaultShareTimelock: null,
+        revocationCooldown: null,
+      })
+      .accounts({ authority: provider.wallet.publicKey })
+      .rpc();
+
+  // Withdraws the holder's refund and checks it is the snapshot's per-pass share
+  const withdrawAndCheck = async (
+    creatorPool: PublicKey,
+    solVault: PublicKey,
+    passMint: PublicKey,
+    holder: Keypair
+  ) => {
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    const share = Math.floor(
+      pool.refundSnapshotLamports.toNumber() /
+        pool.refundSnapshotSupply.toNumber()
+    );
+    const refundReceipt = pda("pool_refund", creatorPool, passMint);
+
+    const before = await balance(solVault);
+    await program.methods
+      .withdrawPoolRefund()
+      .accountsPartial({
+        creatorPool,
+        solVault,
+        nftOwnership: pda("nft_ownership", passMint),
+        refundReceipt,
+        holder: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc();
+    const after = await balance(solVault);
+
+    assert.equal(before - after, share);
+    const receipt = await program.account.poolRefundReceipt.fetch(refundReceipt);
+    assert.equal(receipt.amount.toNumber(), share);
+  };
+
+  it("lets a holder trigger a refund once the creator stops sending heartbeats", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    const otherFan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    await airdrop(otherFan.publicKey, 5);
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator);
+    const passMint = await buyPass(creator.publicKey, fan);
+    await buyPass(creator.publicKey, otherFan);
+
+    const trigger = () =>
+      program.methods
+        .triggerInactivityRefund()
+        .accountsPartial({
+          creatorPool,
+          creatorCollection,
+          solVault,
+          nftOwnership: pda("nft_ownership", passMint),
+          holder: fan.publicKey,
+          factory: pda("factory"),
+        })
+        .signers([fan])
+        .rpc();
+
+    let unreserved = 0;
+    await setInactivityPeriod(3);
+    try {
+      await program.methods
+        .heartbeat()
+        .accounts({ creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      try {
+        await trigger();
+        assert.fail("refund triggered while the creator was active");
+      } catch (err) {
+        assert.include(String(err), "CreatorStillActive");
+      }
+
+      await sleep(5_000);
+      unreserved = await unreservedVault(solVault);
+      await trigger();
+    } finally {
+      await setInactivityPeriod(0);
+    }
+
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    assert.deepEqual(pool.status, { refunding: {} });
+    assert.equal(pool.refundSnapshotSupply.toNumber(), 2);
+    assert.equal(pool.refundSnapshotLamports.toNumber(), unreserved);
+
+    await withdrawAndCheck(creatorPool, solVault, passMint, fan);
+  });
+
+  it("refunds holders when a misconduct proposal passes", async () => {
+    const creator = Keypair.generate();
+    const fan = Keypair.generate();
+    await airdrop(fan.publicKey, 5);
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(creator, { votingWindow: 2 });
+    const passMint = await buyPass(creator.publicKey, fan);
+    const nftOwnership = pda("nft_ownership", passMint);
+
+    // With no co-sign threshold the proposal opens straight into voting
+    const proposal = pda(
+      "misconduct",
+      creatorPool,
+      new anchor.BN(0).toArrayLike(Buffer, "le", 8)
+    );
+    await program.methods
+      .openMisconductProposal("QmEvidence")
+      .accountsPartial({
+        proposal,
+        creatorPool,
+        nftOwnership,
+        cosign: pda("proposal_cosign", proposal, passMint),
+        proposer: fan.publicKey,
+        factory: pda("factory"),
+      })
+      .signers([fan])
+      .rpc();
+    await program.methods
+      .voteMisconductProposal({ yes: {} })
+      .accountsPartial({
+        proposal,
+        creatorPool,
+        nftOwnership,
+        proposalVote: pda("proposal_vote", proposal, passMint),
+        voter: fan.publicKey,
+      })
+      .signers([fan])
+      .rpc();
+
+    await sleep(3_000);
+    const unreserved = await unreservedVault(solVault);
+    await program.methods
+      .finalizeMisconductProposal()
+      .accountsPartial({ proposal, creatorPool, creatorCollection, solVault })
+      .rpc();
+
+    const finalized = await program.account.misconductProposal.fetch(proposal);
+    assert.deepEqual(finalized.status, { passed: {} });
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    assert.deepEqual(pool.status, { refunding: {} });
+    assert.equal(pool.refundSnapshotLamports.toNumber(), unreserved);
+
+    await withdrawAndCheck(creatorPool, solVault, passMint, fan);
+  });
+});
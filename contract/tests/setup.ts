@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { Contract } from "../target/types/contract";
+
+// Shared local-validator setup: anchor test --provider.cluster localnet
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.contract as Program<Contract>;
+export const connection = provider.connection;
+
+export const PASS_PRICE = new anchor.BN(LAMPORTS_PER_SOL);
+
+export type CollectionOptions = {
+  referralBps?: number;
+  royaltyBps?: number;
+  exitFeeBps?: number;
+  maxSupply?: number;
+  passDuration?: number;
+  votingWindow?: number;
+};
+
+export const pda = (...seeds: (string | PublicKey | Buffer)[]) =>
+  PublicKey.findProgramAddressSync(
+    seeds.map((seed) =>
+      typeof seed === "string"
+        ? Buffer.from(seed)
+        : seed instanceof PublicKey
+        ? seed.toBuffer()
+        : seed
+    ),
+    program.programId
+  )[0];
+
+export const balance = (address: PublicKey) =>
+  connection.getBalance(address, "confirmed");
+
+export const airdrop = async (to: PublicKey, sol: number) => {
+  const signature = await connection.requestAirdrop(
+    to,
+    sol * LAMPORTS_PER_SOL
+  );
+  await connection.confirmTransaction(signature, "confirmed");
+};
+
+export const sleep = (ms: number) =>
+  new Promise((resolve) => setTimeout(resolve, ms));
+
+export const passAccount = (passMint: PublicKey, holder: PublicKey) =>
+  getAssociatedTokenAddressSync(passMint, holder, true, TOKEN_2022_PROGRAM_ID);
+
+// Mirrors fees::bps_of, which rounds down
+export const bpsOf = (amount: number, bps: number) =>
+  Math.floor((amount * bps) / 10_000);
+
+// The vault keeps its rent-exempt minimum out of every payout
+export const unreservedVault = async (solVault: PublicKey) =>
+  (await balance(solVault)) -
+  (await connection.getMinimumBalanceForRentExemption(0));
+
+const initializeFactory = async (payer: Keypair) => {
+  if (await connection.getAccountInfo(pda("factory"))) return;
+
+  const usdcMint = await createMint(
+    connection,
+    payer,
+    payer.publicKey,
+    null,
+    6
+  );
+  await program.methods
+    .initializeFactory(
+      new anchor.BN(1),
+      new anchor.BN(60),
+      new anchor.BN(500),
+      new anchor.BN(2_000)
+    )
+    .accounts({ authority: provider.wallet.publicKey, usdcMint })
+    .rpc();
+  await program.methods
+    .initializeTreasury()
+    .accounts({ authority: provider.wallet.publicKey })
+    .rpc();
+};
+
+// Funds the creator and opens their pool and pass collection
+export const createCollection = async (
+  creator: Keypair,
+  options: CollectionOptions = {}
+) => {
+  await airdrop(creator.publicKey, 10);
+  await initializeFactory(creator);
+
+  await program.methods
+    .createPool(
+      new anchor.BN(1),
+      new anchor.BN(options.votingWindow ?? 60),
+      new anchor.BN(0)
+    )
+    .accounts({ creator: creator.publicKey })
+    .signers([creator])
+    .rpc();
+
+  const collectionMint = await createMint(
+    connection,
+    creator,
+    creator.publicKey,
+    null,
+    0
+  );
+  await program.methods
+    .createCollection({
+      basePrice: PASS_PRICE,
+      priceCurve: { flat: {} },
+      maxSupply: new anchor.BN(options.maxSupply ?? 0),
+      maxPerWallet: new anchor.BN(0),
+      referralBps: new anchor.BN(options.referralBps ?? 0),
+      royaltyBps: new anchor.BN(options.royaltyBps ?? 0),
+      soulbound: false,
+      exitFeeBps: new anchor.BN(options.exitFeeBps ?? 0),
+      passDuration: new anchor.BN(options.passDuration ?? 0),
+      renewalPrice: new anchor.BN(0),
+    })
+    .accounts({
+      creator: creator.publicKey,
+      collectionMint,
+      collectionMetadata: Keypair.generate().publicKey,
+      collectionMasterEdition: Keypair.generate().publicKey,
+    })
+    .signers([creator])
+    .rpc();
+
+  return {
+    creatorCollection: pda("creator_collection", creator.publicKey),
+    creatorPool: pda("creator_pool", creator.publicKey),
+    solVault: pda("sol_vault", creator.publicKey),
+  };
+};
+
+// Buys the collection's next pass at PASS_PRICE and returns its mint
+export const buyPass = async (
+  creator: PublicKey,
+  buyer: Keypair,
+  referrer: PublicKey | null = null
+) => {
+  const passMint = Keypair.generate();
+  await program.methods
+    .buyPass(PASS_PRICE, [])
+    .accountsPartial({
+      creatorCollection: pda("creator_collection", creator),
+      creatorWallet: creator,
+      passMint: passMint.publicKey,
+      buyerTokenAccount: passAccount(passMint.publicKey, buyer.publicKey),
+      buyer: buyer.publicKey,
+      referrerStats: referrer ? pda("referrer_stats", referrer) : null,
+      referrer,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
+    .signers([buyer, passMint])
+    .rpc();
+
+  return passMint.publicKey;
+};
+
+// Files the pool's next claim and returns its address
+export const fileClaim = async (creator: Keypair) => {
+  const creatorPool = pda("creator_pool", creator.publicKey);
+  const pool = await program.account.creatorPool.fetch(creatorPool);
+  const claim = pda(
+    "claim",
+    creatorPool,
+    pool.claimCount.toArrayLike(Buffer, "le", 8)
+  );
+
+  await program.methods
+    .fileClaim("QmClaimEvidence", pool.claimCount, creatorPool)
+    .accountsPartial({
+      claim,
+      creatorPool,
+      creator: creator.publicKey,
+      creatorUsdcAccount: creator.publicKey,
+      creatorPoolVault: pda("sol_vault", creator.publicKey),
+      claimBond: pda("claim_bond", claim),
+      factory: pda("factory"),
+      creatorCollection: pda("creator_collection", creator.publicKey),
+    })
+    .signers([creator])
+    .rpc();
+
+  return claim;
+};
@@ -0,0 +1,171 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  createTransferCheckedWithTransferHookInstruction,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  airdrop,
+  balance,
+  bpsOf,
+  buyPass,
+  connection,
+  createCollection,
+  passAccount,
+  pda,
+  PASS_PRICE,
+  program,
+  provider,
+  sleep,
+} from "./setup";
+
+describe("pass transfer hook", () => {
+  const creator = Keypair.generate();
+  const fan = Keypair.generate();
+  const recipient = Keypair.generate();
+  let creatorCollection: PublicKey;
+  let passMint: PublicKey;
+  let nftOwnership: PublicKey;
+
+  // Sends the pass from a wallet with a plain transfer_checked, creating the recipient's ATA first
+  const walletTransfer = async (
+    mint: PublicKey,
+    from: Keypair,
+    to: PublicKey
+  ) => {
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createAssociatedTokenAccountIdempotentInstruction(
+          from.publicKey,
+          passAccount(mint, to),
+          to,
+          mint,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [from]
+    );
+
+    const transfer = await createTransferCheckedWithTransferHookInstruction(
+      connection,
+      passAccount(mint, from.publicKey),
+      mint,
+      passAccount(mint, to),
+      from.publicKey,
+      BigInt(1),
+      0,
+      [],
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    await provider.sendAndConfirm(new Transaction().add(transfer), [from]);
+  };
+
+  before(async () => {
+    await airdrop(fan.publicKey, 10);
+    await airdrop(recipient.publicKey, 1);
+    ({ creatorCollection } = await createCollection(creator));
+    passMint = await buyPass(creator.publicKey, fan);
+    nftOwnership = pda("nft_ownership", passMint);
+  });
+
+  it("registers the buyer as owner on purchase", async () => {
+    const ownership = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.ok(ownership.owner.equals(fan.publicKey));
+    assert.ok(ownership.creator.equals(creator.publicKey));
+  });
+
+  it("moves nft_ownership to the recipient on a wallet transfer", async () => {
+    const before = await program.account.nftOwnership.fetch(nftOwnership);
+
+    // Let the clock tick so the new acquisition time is distinguishable
+    await sleep(1_500);
+    await walletTransfer(passMint, fan, recipient.publicKey);
+
+    const ownership = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.ok(ownership.owner.equals(recipient.publicKey));
+    assert.isTrue(ownership.acquiredAt.gt(before.acquiredAt));
+  });
+
+  it("rejects calling the hook outside of a transfer", async () => {
+    try {
+      await program.methods
+        .transferHook(new anchor.BN(1))
+        .accountsPartial({
+          sourceToken: passAccount(passMint, recipient.publicKey),
+          mint: passMint,
+          destinationToken: passAccount(passMint, fan.publicKey),
+          owner: fan.publicKey,
+          nftOwnership,
+          creatorCollection,
+        })
+        .rpc();
+      assert.fail("direct hook call should fail");
+    } catch (err) {
+      assert.include(String(err), "NotTransferring");
+    }
+
+    const ownership = await program.account.nftOwnership.fetch(nftOwnership);
+    assert.ok(ownership.owner.equals(recipient.publicKey));
+  });
+
+  it("only moves a royalty pass once the owner has paid the royalty", async () => {
+    const royaltyCreator = Keypair.generate();
+    const royaltyBps = 1_000;
+    const { creatorCollection, creatorPool, solVault } =
+      await createCollection(royaltyCreator, { royaltyBps });
+    const royaltyMint = await buyPass(royaltyCreator.publicKey, fan);
+    const royaltyOwnership = pda("nft_ownership", royaltyMint);
+
+    try {
+      await walletTransfer(royaltyMint, fan, recipient.publicKey);
+      assert.fail("unapproved transfer should fail");
+    } catch (err) {
+      assert.include(String(err), "TransferNotApproved");
+    }
+
+    // Wallet transfers pay the royalty on the collection's current price
+    const pool = await program.account.creatorPool.fetch(creatorPool);
+    const royalty = bpsOf(PASS_PRICE.toNumber(), royaltyBps);
+    const creatorAmount = bpsOf(
+      royalty,
+      10_000 - pool.vaultShareBps.toNumber()
+    );
+    const transferApproval = pda("transfer_approval", royaltyMint);
+
+    const accounts = [solVault, royaltyCreator.publicKey];
+    const before = await Promise.all(accounts.map(balance));
+    await program.methods
+      .approvePassTransfer(recipient.publicKey)
+      .accountsPartial({
+        transferApproval,
+        nftOwnership: royaltyOwnership,
+        creatorCollection,
+        creatorPool,
+        solVault,
+        creatorWallet: royaltyCreator.publicKey,
+        owner: fan.publicKey,
+      })
+      .signers([fan])
+      .rpc();
+    const after = await Promise.all(accounts.map(balance));
+
+    assert.equal(after[0] - before[0], royalty - creatorAmount);
+    assert.equal(after[1] - before[1], creatorAmount);
+
+    await walletTransfer(royaltyMint, fan, recipient.publicKey);
+
+    const ownership = await program.account.nftOwnership.fetch(
+      royaltyOwnership
+    );
+    assert.ok(ownership.owner.equals(recipient.publicKey));
+
+    // The approval is spent by the transfer it covered
+    const approval = await program.account.transferApproval.fetch(
+      transferApproval
+    );
+    assert.ok(approval.recipient.equals(PublicKey.default));
+  });
+});
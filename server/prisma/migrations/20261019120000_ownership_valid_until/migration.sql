@@ -0,0 +1,2 @@
+-- AlterTable
+ALTER TABLE "Ownership" ADD COLUMN     "validUntil" TIMESTAMP(3);
@@ -2,6 +2,7 @@ import { Response } from "express";
 import { prisma } from "../lib/prisma";
 import { AuthRequest } from "../middleware/auth-middleware";
 import { resolveMediaUrl } from "../lib/image-helper";
+import { unexpiredOwnership } from "../lib/ownership";
 import {
   Connection,
   PublicKey,
@@ -522,7 +523,7 @@ export const refundClaim = async (req: AuthRequest, res: Response) => {
 export const getNFTHoldersForCreator = async (creatorId: string) => {
   try {
     const ownerships = await prisma.ownership.findMany({
-      where: { creatorId },
+      where: { creatorId, ...unexpiredOwnership() },
       include: {
         user: {
           select: {
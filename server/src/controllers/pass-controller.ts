@@ -2,6 +2,11 @@ import { Response } from "express";
 import { prisma } from "../lib/prisma";
 import { AuthRequest } from "../middleware/auth-middleware";
 import { resolveMediaUrl } from "../lib/image-helper";
+import {
+  fetchNftOwnership,
+  isConfirmedTransaction,
+  unexpiredOwnership,
+} from "../lib/ownership";
 
 export const createPass = async (req: AuthRequest, res: Response) => {
   try {
@@ -53,12 +58,11 @@ export const getPass = async (req: AuthRequest, res: Response) => {
     if (!pass) {
       return res.status(404).json({ message: "Creator doesn't own a pass" });
     }
-    const ownership = await prisma.ownership.findUnique({
+    const ownership = await prisma.ownership.findFirst({
       where: {
-        userId_passId: {
-          userId,
-          passId: pass.id,
-        },
+        userId,
+        passId: pass.id,
+        ...unexpiredOwnership(),
       },
     });
     const creatorWithImage = {
@@ -133,6 +137,9 @@ export const buyPass = async (req: AuthRequest, res: Response) => {
     // In production, you might want to add transaction verification
     console.log("📝 Creating ownership record for transaction:", txId);
 
+    // Expiry is read from the pass's on-chain record rather than the client
+    const onchain = nftMint ? await fetchNftOwnership(nftMint) : null;
+
     // Create ownership record
     await prisma.ownership.create({
       data: {
@@ -140,6 +147,7 @@ export const buyPass = async (req: AuthRequest, res: Response) => {
         creatorId: pass.creatorId,
         passId,
         nftMint,
+        validUntil: onchain?.validUntil ?? null,
       },
     });
 
@@ -204,3 +212,41 @@ export const buyPass = async (req: AuthRequest, res: Response) => {
     });
   }
 };
+
+// Records a renew_pass transaction by syncing validUntil from the pass's on-chain record
+export const renewPass = async (req: AuthRequest, res: Response) => {
+  try {
+    const { nftMint, txId } = req.body;
+    const userId = req.user?.userId;
+    if (!userId) {
+      return res.status(401).json({ error: "Unauthorized" });
+    }
+
+    const ownership = await prisma.ownership.findUnique({
+      where: { nftMint },
+    });
+    if (!ownership) {
+      return res.status(404).json({ error: "Ownership not found" });
+    }
+    if (ownership.userId !== userId) {
+      return res.status(403).json({ error: "Pass is not owned by this user" });
+    }
+
+    if (!(await isConfirmedTransaction(txId))) {
+      return res.status(400).json({ error: "Transaction is not confirmed" });
+    }
+    const onchain = await fetchNftOwnership(nftMint);
+    if (!onchain || onchain.revoked) {
+      return res.status(400).json({ error: "Pass is not active on-chain" });
+    }
+
+    const updated = await prisma.ownership.update({
+      where: { nftMint },
+      data: { validUntil: onchain.validUntil },
+    });
+
+    res.status(200).json(updated);
+  } catch (e: any) {
+    res.status(500).json({ message: e.message });
+  }
+};
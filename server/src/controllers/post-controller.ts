@@ -7,6 +7,7 @@ import {
 } from "../lib/storage";
 import { AuthRequest } from "../middleware/auth-middleware";
 import { resolveMediaUrl } from "../lib/image-helper";
+import { unexpiredOwnership } from "../lib/ownership";
 
 export const createPost = async (req: AuthRequest, res: Response) => {
   try {
@@ -53,7 +54,7 @@ export const getPost = async (req: AuthRequest, res: Response) => {
     let ownsPass = false;
     if (post.isPremium) {
       const ownership = await prisma.ownership.findFirst({
-        where: { userId, creatorId: post.creatorId },
+        where: { userId, creatorId: post.creatorId, ...unexpiredOwnership() },
       });
       ownsPass = !!ownership;
     }
@@ -0,0 +1,52 @@
+import { Connection, PublicKey, clusterApiUrl } from "@solana/web3.js";
+
+const CONTRACT_PROGRAM_ID = new PublicKey(
+  "BqHTWrkNFvj9ZA24yFkcTiXdczrNuQpspknnt3tWabVF"
+);
+
+// Byte offsets into the NftOwnership account, after the 8-byte discriminator
+const OWNER_OFFSET = 8;
+const VALID_UNTIL_OFFSET = 154;
+const REVOKED_OFFSET = 170;
+
+export type OnchainOwnership = {
+  owner: string;
+  validUntil: Date | null;
+  revoked: boolean;
+};
+
+// Mirrors NftOwnership.valid_until on-chain: a null validUntil never expires
+export const unexpiredOwnership = () => ({
+  OR: [{ validUntil: null }, { validUntil: { gt: new Date() } }],
+});
+
+// Reads a pass's NftOwnership PDA so expiry comes from the chain, not the request body
+export const fetchNftOwnership = async (
+  nftMint: string
+): Promise<OnchainOwnership | null> => {
+  const connection = new Connection(clusterApiUrl("devnet"), "confirmed");
+  const [address] = PublicKey.findProgramAddressSync(
+    [Buffer.from("nft_ownership"), new PublicKey(nftMint).toBuffer()],
+    CONTRACT_PROGRAM_ID
+  );
+  const account = await connection.getAccountInfo(address);
+  if (!account || !account.owner.equals(CONTRACT_PROGRAM_ID)) return null;
+
+  const validUntil = Number(account.data.readBigInt64LE(VALID_UNTIL_OFFSET));
+  return {
+    owner: new PublicKey(
+      account.data.subarray(OWNER_OFFSET, OWNER_OFFSET + 32)
+    ).toBase58(),
+    validUntil: validUntil > 0 ? new Date(validUntil * 1000) : null,
+    revoked: account.data[REVOKED_OFFSET] === 1,
+  };
+};
+
+// True once the transaction has landed without an error
+export const isConfirmedTransaction = async (txId: string) => {
+  const connection = new Connection(clusterApiUrl("devnet"), "confirmed");
+  const { value } = await connection.getSignatureStatus(txId, {
+    searchTransactionHistory: true,
+  });
+  return !!value && !value.err && value.confirmationStatus !== "processed";
+};
@@ -5,10 +5,16 @@ import {
   buyPass,
   createPass,
   getPass,
+  renewPass,
   updatePass,
 } from "../controllers/pass-controller";
 import { validateData } from "../middleware/validation-middleware";
-import { createPassSchema, updatePassSchema, buyPassSchema } from "../zod/pass-schema";
+import {
+  createPassSchema,
+  updatePassSchema,
+  buyPassSchema,
+  renewPassSchema,
+} from "../zod/pass-schema";
 
 
 const passRouter = Router();
@@ -19,6 +25,8 @@ passRouter.put("/", authenticate, validateData(updatePassSchema), updatePass);
 
 passRouter.post("/buy", authenticate, validateData(buyPassSchema), buyPass);
 
+passRouter.post("/renew", authenticate, validateData(renewPassSchema), renewPass);
+
 passRouter.get("/:creatorId", authenticate, getPass);
 
 export default passRouter;
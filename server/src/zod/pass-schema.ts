@@ -14,3 +14,8 @@ export const buyPassSchema = z.object({
   passId: z.string().uuid("passId must be a valid UUID"),
   txId: z.string().min(1, "Transaction ID is required"),
 });
+
+export const renewPassSchema = z.object({
+  nftMint: z.string().min(1, "nftMint is required"),
+  txId: z.string().min(1, "Transaction ID is required"),
+});